@@ -0,0 +1,89 @@
+//! Compares synthesis and proving time under `SimpleFloorPlanner` and `V1`.
+//!
+//! Run with `cargo bench --bench planner`. Uses plain wall-clock timing, since this crate
+//! has no benchmarking harness dependency.
+
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use halo2_fibonacci::{Config, FibonacciCircuit};
+use halo2_proofs::circuit::{floor_planner::V1, Layouter, SimpleFloorPlanner};
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{
+    create_proof, keygen_pk, keygen_vk, Circuit, ConstraintSystem, Error, FloorPlanner,
+};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::transcript::{Blake2bWrite, Challenge255};
+use rand_core::OsRng;
+
+/// The Fibonacci circuit laid out by the floor planner `P`.
+struct Planned<P>(FibonacciCircuit<Fp>, PhantomData<P>);
+
+impl<P: FloorPlanner> Circuit<Fp> for Planned<P> {
+    type Config = Config;
+
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses(), PhantomData)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        FibonacciCircuit::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        self.0.synthesize(config, layouter)
+    }
+}
+
+/// The smallest `k` the planner's layout fits in, with the time of that mock synthesis.
+fn measure_k<P: FloorPlanner>(circuit: &Planned<P>, output: Fp) -> (u32, Duration) {
+    (1..=20)
+        .find_map(|k| {
+            let start = Instant::now();
+            let prover = MockProver::run(k, circuit, vec![vec![output]]).ok()?;
+            let elapsed = start.elapsed();
+            prover.verify().ok().map(|()| (k, elapsed))
+        })
+        .expect("circuit fits in k = 20")
+}
+
+fn prove_time<P: FloorPlanner>(circuit: &Planned<P>, output: Fp, k: u32) -> Duration {
+    let params: Params<EqAffine> = Params::new(k);
+    let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+
+    let start = Instant::now();
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof(
+        &params,
+        &pk,
+        std::slice::from_ref(circuit),
+        &[&[&[output]]],
+        OsRng,
+        &mut transcript,
+    )
+    .unwrap();
+    start.elapsed()
+}
+
+fn bench<P: FloorPlanner>(name: &str, num_terms: usize) {
+    let (inner, public_inputs) = FibonacciCircuit::<Fp>::random(OsRng, num_terms);
+    let output = public_inputs[0];
+    let circuit = Planned::<P>(inner, PhantomData);
+    let (k, synthesis) = measure_k(&circuit, output);
+    let proving = prove_time(&circuit, output, k);
+    println!(
+        "{:>18} num_terms = {:>4}: k = {:>2}, synthesis {:>10.2?}, proving {:>10.2?}",
+        name, num_terms, k, synthesis, proving
+    );
+}
+
+fn main() {
+    for num_terms in [100, 1000] {
+        bench::<SimpleFloorPlanner>("SimpleFloorPlanner", num_terms);
+        bench::<V1>("V1", num_terms);
+    }
+}
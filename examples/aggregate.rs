@@ -0,0 +1,26 @@
+//! Proves three Fibonacci statements of the same shape and checks them with a single batch
+//! verification.
+//!
+//! `halo2_proofs` 0.2 only has the IPA backend, so the proofs can't be recursively folded
+//! into one; batching still amortizes the expensive final check across all of them.
+
+use halo2_fibonacci::{min_k, prove, verify_batch, FibonacciCircuit};
+use halo2_proofs::pasta::Fp;
+
+fn main() {
+    let num_terms = 10;
+    let k = min_k(num_terms);
+    let proofs: Vec<_> = [(1, 1), (2, 1), (3, 7)]
+        .iter()
+        .map(|&(a, b)| {
+            let circuit = FibonacciCircuit::<Fp>::new(a, b, num_terms);
+            prove(&circuit, k).expect("proving succeeds")
+        })
+        .collect();
+
+    for proof in &proofs {
+        println!("F({}) = {:?}", proof.num_terms, proof.public_inputs[0]);
+    }
+    assert!(verify_batch(&proofs).is_ok());
+    println!("{} proofs verified in one batch", proofs.len());
+}
@@ -0,0 +1,150 @@
+/*
+
+    Several independent Fibonacci statements in one circuit. Every sequence is laid out by
+    the same chip in its own regions, stacked in the shared columns, and the public inputs
+    of each (per its exposure flags) get the next free instance rows:
+
+    | elem_1 | elem_2 | elem_3 | q_fib | instance
+    ---------------------------------------------
+    |   a_1  |   b_1  |  ...   |   1   | F_1(n_1)
+    |  ...   |  ...   |  ...   |   1   | F_2(n_2)
+    |   a_2  |   b_2  |  ...   |   1   | ...
+    |  ...   |  ...   |  ...   |   1   |
+
+*/
+
+use std::ops::Range;
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner};
+use halo2_proofs::plonk::*;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::k_for_rows;
+
+/// A set of [`FibonacciCircuit`]s proven together, each with its own non-overlapping run
+/// of instance rows.
+#[derive(Clone, Debug, Default)]
+pub struct FibonacciBattery<F: FieldExt> {
+    chips: Vec<FibonacciCircuit<F>>,
+}
+
+impl<F: FieldExt> FibonacciBattery<F> {
+    pub fn new() -> Self {
+        Self { chips: Vec::new() }
+    }
+
+    /// Adds `circuit` as the next chip; its public inputs follow those of the chips added
+    /// before it.
+    pub fn with_chip(mut self, circuit: FibonacciCircuit<F>) -> Self {
+        self.chips.push(circuit);
+        self
+    }
+
+    pub fn chips(&self) -> &[FibonacciCircuit<F>] {
+        &self.chips
+    }
+
+    /// The instance rows allocated to each chip, in the order the chips were added.
+    pub fn instance_rows(&self) -> Vec<Range<usize>> {
+        let mut start = 0;
+        self.chips
+            .iter()
+            .map(|chip| {
+                let rows = start..start + chip.num_public_inputs();
+                start = rows.end;
+                rows
+            })
+            .collect()
+    }
+
+    /// Checks every chip, and that there is at least one.
+    pub fn validate(&self) -> Result<(), FibError> {
+        if self.chips.is_empty() {
+            return Err(FibError::BadPublicInputs(
+                "the battery has no chips".to_string(),
+            ));
+        }
+        self.chips.iter().try_for_each(FibonacciCircuit::validate)
+    }
+
+    /// The public inputs of every chip, concatenated in the order of
+    /// [`Self::instance_rows`], or `None` for a circuit without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let inputs: Option<Vec<Vec<F>>> = self
+            .chips
+            .iter()
+            .map(FibonacciCircuit::public_inputs)
+            .collect();
+        Some(inputs?.concat())
+    }
+
+    /// The smallest `k` fitting every chip's rows.
+    pub fn k(&self) -> u32 {
+        k_for_rows(self.chips.iter().map(|chip| chip.rows().max(1)).sum())
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for FibonacciBattery<F> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            chips: self.chips.iter().map(Circuit::without_witnesses).collect(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Config::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        for (chip, rows) in self.chips.iter().zip(self.instance_rows()) {
+            chip.synthesize_at(config, layouter.namespace(|| "chip"), rows.start)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::proof::{create_circuit_proof, verify_circuit_proof};
+
+    #[test]
+    fn test_three_sequences_in_one_proof() {
+        let battery = FibonacciBattery::new()
+            .with_chip(FibonacciCircuit::new(1, 1, 10))
+            .with_chip(FibonacciCircuit::new(2, 1, 12).expose_seeds(true))
+            .with_chip(FibonacciCircuit::new(3, 7, 5));
+        battery.validate().unwrap();
+        assert_eq!(battery.instance_rows(), vec![0..1, 1..4, 4..5]);
+
+        // F(10) = 55, Lucas L(12) = 199 with its seeds, and 3, 7, 10, 17, 27
+        let public_inputs = battery.public_inputs().unwrap();
+        let expected: Vec<Fp> = [55, 2, 1, 199, 27].into_iter().map(Fp::from).collect();
+        assert_eq!(public_inputs, expected);
+
+        let k = battery.k();
+        let proof = create_circuit_proof(&battery, &public_inputs, k).unwrap();
+        verify_circuit_proof(&battery, &public_inputs, &proof, k).unwrap();
+
+        let mut forged = public_inputs;
+        forged[4] += Fp::one();
+        assert!(matches!(
+            verify_circuit_proof(&battery, &forged, &proof, k),
+            Err(FibError::Verify)
+        ));
+
+        assert!(FibonacciBattery::<Fp>::new().validate().is_err());
+    }
+}
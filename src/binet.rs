@@ -0,0 +1,227 @@
+/*
+
+    F(n) cross-checked against the closed form phi^n = F(n-1) + F(n) * phi.
+
+    5 is not a square in the Pasta fields, so phi = (1 + sqrt 5) / 2 is not a field element.
+    Instead it lives in the extension Fp[phi] / (phi^2 - phi - 1), whose elements are pairs
+    x + y * phi, and phi^n is computed by square-and-multiply over the bits of n (most
+    significant first), reducing with phi^2 = phi + 1:
+
+    (x + y phi)^2 = (x^2 + y^2) + (2xy + y^2) phi = p + q phi
+    (p + q phi) * phi = q + (p + q) phi
+
+    | elem_1 | elem_2 |   bit    | q_start | q_square
+    -------------------------------------------------
+    |    1   |    0   |  b_{L-1} |    1    |    1
+    |   ...  |   ...  |   ...    |    0    |    1
+    |  x_L   |   y_L  |          |    0    |    0
+
+    q_start * (elem_1 - 1) = 0,  q_start * elem_2 = 0
+    q_square * (elem_1(next) - (p + bit * (q - p))) = 0
+    q_square * (elem_2(next) - (q + bit * p)) = 0
+
+    The phi coefficient y_L of phi^n is copied to F(n) from the recurrence. There is no
+    rounding: in a field the closed form holds exactly.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct BinetConfig {
+    fib: Config,
+    bit: Column<Fixed>,
+    q_start: Selector,
+    q_square: Selector,
+}
+
+impl BinetConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let bit = cs.fixed_column();
+        let q_start = cs.selector();
+        let q_square = cs.selector();
+
+        cs.create_gate("binet start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(q_start);
+            let x = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let y = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            vec![
+                q_start.clone() * (x - Expression::Constant(F::one())),
+                q_start * y,
+            ]
+        });
+
+        cs.create_gate("binet square", |virtual_cells| {
+            let q_square = virtual_cells.query_selector(q_square);
+            let x = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let y = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let x_next = virtual_cells.query_advice(fib.elem_1, Rotation::next());
+            let y_next = virtual_cells.query_advice(fib.elem_2, Rotation::next());
+            let bit = virtual_cells.query_fixed(bit, Rotation::cur());
+
+            let p = x.clone() * x.clone() + y.clone() * y.clone();
+            let q = Expression::Constant(F::from(2)) * x * y.clone() + y.clone() * y;
+            vec![
+                q_square.clone() * (x_next - (p.clone() + bit.clone() * (q.clone() - p.clone()))),
+                q_square * (y_next - (q + bit * p)),
+            ]
+        });
+
+        Self {
+            fib,
+            bit,
+            q_start,
+            q_square,
+        }
+    }
+}
+
+/// Circuit proving `F(n)` (standard `1, 1` seeds) both by the recurrence and by computing
+/// `phi^n`, exposing `F(n)`.
+#[derive(Clone, Debug)]
+pub struct BinetCircuit {
+    n: usize,
+    witness: bool,
+}
+
+impl BinetCircuit {
+    pub fn new(n: usize) -> Result<Self, FibError> {
+        if n < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                n
+            )));
+        }
+        Ok(Self { n, witness: true })
+    }
+
+    /// The bits of `n`, most significant first.
+    fn bits(&self) -> Vec<bool> {
+        let len = usize::BITS - self.n.leading_zeros();
+        (0..len).rev().map(|i| (self.n >> i) & 1 == 1).collect()
+    }
+
+    fn k(&self) -> u32 {
+        // the sequence rows, then one row per bit plus the result row
+        k_for_rows(self.n - 2 + self.bits().len() + 1)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for BinetCircuit {
+    type Config = BinetConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BinetConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let seed = if self.witness {
+            Value::known(F::one())
+        } else {
+            Value::unknown()
+        };
+        let terms = fib.assign_terms(layouter.namespace(|| "sequence"), seed, seed, self.n)?;
+        let output = &terms[self.n - 1];
+
+        layouter.assign_region(
+            || "binet",
+            |mut region| {
+                config.q_start.enable(&mut region, 0)?;
+                let one = seed.map(|_| F::one());
+                let (mut x, mut y) = (one, one.map(|_| F::zero()));
+                for (offset, &bit) in self.bits().iter().enumerate() {
+                    config.q_square.enable(&mut region, offset)?;
+                    region.assign_fixed(
+                        || "bit",
+                        config.bit,
+                        offset,
+                        || Value::known(F::from(bit as u64)),
+                    )?;
+                    region.assign_advice(|| "x", fib.elem_1, offset, || x)?;
+                    region.assign_advice(|| "y", fib.elem_2, offset, || y)?;
+
+                    let p = x * x + y * y;
+                    let q = x * y + x * y + y * y;
+                    (x, y) = if bit { (q, p + q) } else { (p, q) };
+                }
+                let offset = self.bits().len();
+                region.assign_advice(|| "x", fib.elem_1, offset, || x)?;
+                let y = region.assign_advice(|| "y", fib.elem_2, offset, || y)?;
+                region.constrain_equal(y.cell(), output.cell())
+            },
+        )?;
+
+        fib.expose_public(layouter, output, 0)
+    }
+}
+
+/// Proves `F(n)`, cross-checked against the closed form.
+pub fn prove_binet(n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = BinetCircuit::new(n)?;
+    let output = FibonacciCircuit::<Fp>::new(1, 1, n)
+        .output()
+        .expect("seeds are known");
+    let public_inputs = vec![output];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_binet`].
+pub fn verify_binet(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = BinetCircuit::new(proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_binet_matches_recurrence() {
+        for n in 3..=20 {
+            let circuit = BinetCircuit::new(n).unwrap();
+            let output = FibonacciCircuit::<Fp>::new(1, 1, n).output().unwrap();
+            let prover = MockProver::run(circuit.k(), &circuit, vec![vec![output]]).unwrap();
+            prover.assert_satisfied();
+        }
+
+        let circuit = BinetCircuit::new(10).unwrap();
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(56)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let proof = prove_binet(10).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(55)]);
+        verify_binet(&proof).unwrap();
+    }
+}
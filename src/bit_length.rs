@@ -0,0 +1,236 @@
+/*
+
+    Exposes the bit length of F(n). The output is decomposed as in the parity circuit,
+    most significant bit first, and two more columns track whether a set bit has been seen
+    and how many rows have passed since:
+
+    | elem_1 | elem_2 |  seen  | elem_3 | q_start | q_bits
+    ------------------------------------------------------
+    |  b_63  |  acc_0 | seen_0 |  len_0 |    1    |   1
+    |  b_62  |  acc_1 | seen_1 |  len_1 |    0    |   1
+    |  ...   |  ...   |  ...   |  ...   |   ...   |  ...
+    |  b_0   | acc_63 | seen_63| len_63 |    0    |   0
+
+    q_start * (seen - bit) = 0
+    q_start * (len - seen) = 0
+    q_bits * (seen(next) - seen - bit(next) + seen * bit(next)) = 0
+    q_bits * (len(next) - len - seen(next)) = 0
+
+    seen is the OR of the bits so far, so it is boolean whenever the bits are, and len
+    counts the rows from the most significant set bit on. Both seeds are pinned to 1, acc_63
+    is copied from F(n) and len_63 to the instance.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::parity::{ParityConfig, BITS};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::{fib_u64, MAX_U64_INDEX};
+
+#[derive(Clone, Debug)]
+pub struct BitLengthConfig {
+    bits: ParityConfig,
+    seen: Column<Advice>,
+}
+
+impl BitLengthConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let bits = ParityConfig::configure(cs);
+        let seen = cs.advice_column();
+        // pins the seeds
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let (bit, len) = (bits.fib.elem_1, bits.fib.elem_3);
+
+        cs.create_gate("bit length start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(bits.q_start);
+            let bit = virtual_cells.query_advice(bit, Rotation::cur());
+            let seen = virtual_cells.query_advice(seen, Rotation::cur());
+            let len = virtual_cells.query_advice(len, Rotation::cur());
+            vec![
+                q_start.clone() * (seen.clone() - bit),
+                q_start * (len - seen),
+            ]
+        });
+
+        cs.create_gate("bit length step", |virtual_cells| {
+            let q_bits = virtual_cells.query_selector(bits.q_bits);
+            let bit_next = virtual_cells.query_advice(bit, Rotation::next());
+            let seen_cur = virtual_cells.query_advice(seen, Rotation::cur());
+            let seen_next = virtual_cells.query_advice(seen, Rotation::next());
+            let len_cur = virtual_cells.query_advice(len, Rotation::cur());
+            let len_next = virtual_cells.query_advice(len, Rotation::next());
+            vec![
+                q_bits.clone()
+                    * (seen_next.clone() - seen_cur.clone() - bit_next.clone()
+                        + seen_cur * bit_next),
+                q_bits * (len_next - len_cur - seen_next),
+            ]
+        });
+
+        Self { bits, seen }
+    }
+}
+
+/// Circuit proving the bit length of `F(n)` (standard `1, 1` seeds) without revealing
+/// `F(n)`.
+#[derive(Clone, Debug)]
+pub struct BitLengthCircuit {
+    n: usize,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl BitLengthCircuit {
+    pub fn new(n: usize) -> Result<Self, FibError> {
+        if !(3..=MAX_U64_INDEX).contains(&n) {
+            return Err(FibError::BadPublicInputs(format!(
+                "the bit-length circuit needs 3 <= n <= {}, got {}",
+                MAX_U64_INDEX, n
+            )));
+        }
+        Ok(Self {
+            n,
+            seed: 1,
+            witness: true,
+        })
+    }
+
+    /// The number of bits of `F(n)`, the public input.
+    pub fn bit_length(&self) -> u64 {
+        (u64::BITS - fib_u64(self.n).leading_zeros()) as u64
+    }
+
+    fn k(&self) -> u32 {
+        k_for_rows(self.n - 2 + BITS)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for BitLengthCircuit {
+    type Config = BitLengthConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BitLengthConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.bits.fib;
+        let witness = |x: u64| {
+            if self.witness {
+                Value::known(x)
+            } else {
+                Value::unknown()
+            }
+        };
+        let seed = witness(self.seed).map(F::from);
+        let terms = fib.assign_terms(layouter.namespace(|| "sequence"), seed, seed, self.n)?;
+        let output = &terms[self.n - 1];
+
+        let value = witness(self.seed * fib_u64(self.n));
+        let len = layouter.assign_region(
+            || "bit length",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::one())?;
+
+                let cells = config.bits.assign_bits(&mut region, value)?;
+                let (_, acc) = cells.last().expect("at least one bit");
+                region.constrain_equal(acc.cell(), output.cell())?;
+
+                let mut len = None;
+                for offset in 0..BITS {
+                    let shift = BITS - 1 - offset;
+                    // the bits above and at `shift`, nonzero once a set bit has been seen
+                    let prefix = value.map(|value| value >> shift);
+                    let seen = prefix.map(|prefix| F::from((prefix != 0) as u64));
+                    let count =
+                        prefix.map(|prefix| F::from((u64::BITS - prefix.leading_zeros()) as u64));
+                    region.assign_advice(|| "seen", config.seen, offset, || seen)?;
+                    len = Some(region.assign_advice(|| "len", fib.elem_3, offset, || count)?);
+                }
+                Ok(len.expect("at least one bit"))
+            },
+        )?;
+
+        fib.expose_public(layouter, &len, 0)
+    }
+}
+
+/// Proves the bit length of `F(n)`, keeping `F(n)` itself private.
+pub fn prove_bit_length(n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = BitLengthCircuit::new(n)?;
+    let public_inputs = vec![Fp::from(circuit.bit_length())];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_bit_length`].
+pub fn verify_bit_length(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = BitLengthCircuit::new(proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_bit_length_of_f20() {
+        // F(20) = 6765 = 0b1101001101101
+        let proof = prove_bit_length(20).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(13)]);
+        verify_bit_length(&proof).unwrap();
+
+        let mut forged = proof;
+        forged.public_inputs = vec![Fp::from(12)];
+        assert!(matches!(verify_bit_length(&forged), Err(FibError::Verify)));
+
+        // F(3) = 2 and the largest u64 term, F(93) > 2^63
+        for (n, bits) in [(3, 2), (MAX_U64_INDEX, 64)] {
+            let circuit = BitLengthCircuit::new(n).unwrap();
+            assert_eq!(circuit.bit_length(), bits);
+            let instance = vec![Fp::from(bits)];
+            let prover = MockProver::run(circuit.k(), &circuit, vec![instance]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 2 make F(20) = 13530, one bit longer
+        let circuit = BitLengthCircuit {
+            seed: 2,
+            ..BitLengthCircuit::new(20).unwrap()
+        };
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(14)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,303 @@
+/*
+
+    Keeps every term in the lower half of the field, so no term can stand for a "negative"
+    value. Each term is decomposed into 23 limbs of 11 bits, most significant first, with
+    a running sum, and every limb is looked up in a table of 0..2^11:
+
+    | elem_1 | elem_2 | q_start | q_limbs | q_range
+    -----------------------------------------------
+    |  l_22  |  acc_0 |    1    |    1    |    1
+    |  l_21  |  acc_1 |    0    |    1    |    1
+    |  ...   |  ...   |   ...   |   ...   |   ...
+    |  l_0   | acc_22 |    0    |    0    |    1
+
+    q_start * (acc - limb) = 0
+    q_limbs * (acc(next) - 2^11 * acc - limb(next)) = 0
+    q_range * limb in 0..2^11
+
+    acc_22 is copied from the term, which is then below 2^253 <= (p - 1) / 2 for both
+    Pasta fields. With the standard seeds, F(366) is the last term under the bound.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+pub(crate) const LIMB_BITS: usize = 11;
+pub(crate) const LIMBS: usize = 23;
+
+#[derive(Clone, Debug)]
+pub struct BoundedConfig {
+    fib: Config,
+    q_start: Selector,
+    q_limbs: Selector,
+    q_range: Selector,
+    limbs: TableColumn,
+}
+
+impl BoundedConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let (limb, acc) = (fib.elem_1, fib.elem_2);
+        let q_start = cs.selector();
+        let q_limbs = cs.selector();
+        let q_range = cs.complex_selector();
+        let limbs = cs.lookup_table_column();
+
+        cs.create_gate("range start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(q_start);
+            let limb = virtual_cells.query_advice(limb, Rotation::cur());
+            let acc = virtual_cells.query_advice(acc, Rotation::cur());
+            vec![q_start * (acc - limb)]
+        });
+
+        cs.create_gate("range step", |virtual_cells| {
+            let q_limbs = virtual_cells.query_selector(q_limbs);
+            let acc_cur = virtual_cells.query_advice(acc, Rotation::cur());
+            let acc_next = virtual_cells.query_advice(acc, Rotation::next());
+            let limb_next = virtual_cells.query_advice(limb, Rotation::next());
+            let shift = Expression::Constant(F::from(1 << LIMB_BITS));
+            vec![q_limbs * (acc_next - shift * acc_cur - limb_next)]
+        });
+
+        cs.lookup(|virtual_cells| {
+            let q_range = virtual_cells.query_selector(q_range);
+            let limb = virtual_cells.query_advice(limb, Rotation::cur());
+            vec![(q_range * limb, limbs)]
+        });
+
+        Self {
+            fib,
+            q_start,
+            q_limbs,
+            q_range,
+            limbs,
+        }
+    }
+
+    pub(crate) fn load_table<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "limbs",
+            |mut table| {
+                for limb in 0..1 << LIMB_BITS {
+                    table.assign_cell(
+                        || "limb",
+                        self.limbs,
+                        limb,
+                        || Value::known(F::from(limb as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Lays out the sequence like [`Config::assign_terms`] and range checks every term,
+    /// returning the cells of `F(1)..=F(num_terms)`.
+    pub fn assign<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let terms =
+            self.fib
+                .assign_terms(layouter.namespace(|| "sequence"), elem_1, elem_2, num_terms)?;
+        for term in &terms {
+            self.range_check(layouter.namespace(|| "range check"), term)?;
+        }
+        Ok(terms)
+    }
+
+    fn range_check<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        term: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        let limbs = term.value().map(|term| limbs(*term));
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                let mut acc = term.value().map(|_| F::zero());
+                let mut last = None;
+                for offset in 0..LIMBS {
+                    self.q_range.enable(&mut region, offset)?;
+                    if offset == 0 {
+                        self.q_start.enable(&mut region, offset)?;
+                    }
+                    if offset < LIMBS - 1 {
+                        self.q_limbs.enable(&mut region, offset)?;
+                    }
+                    let limb = limbs.as_ref().map(|limbs| limbs[offset]);
+                    acc = acc
+                        .zip(limb)
+                        .map(|(acc, limb)| acc * F::from(1 << LIMB_BITS) + limb);
+                    region.assign_advice(|| "limb", self.fib.elem_1, offset, || limb)?;
+                    last = Some(region.assign_advice(|| "acc", self.fib.elem_2, offset, || acc)?);
+                }
+                let acc = last.expect("at least one limb");
+                region.constrain_equal(acc.cell(), term.cell())
+            },
+        )
+    }
+}
+
+/// The limbs of `value`, most significant first. The top limb takes every bit from
+/// `11 * 22` up, so it only fits the table when `value < 2^253`.
+fn limbs<F: FieldExt>(value: F) -> Vec<F> {
+    let repr = value.to_repr();
+    let bit = |i: usize| (repr.as_ref()[i / 8] >> (i % 8)) & 1 == 1;
+    let bits = repr.as_ref().len() * 8;
+    (0..LIMBS)
+        .rev()
+        .map(|limb| {
+            let end = if limb == LIMBS - 1 {
+                bits
+            } else {
+                (limb + 1) * LIMB_BITS
+            };
+            (limb * LIMB_BITS..end)
+                .rev()
+                .fold(F::zero(), |acc, i| acc.double() + F::from(bit(i) as u64))
+        })
+        .collect()
+}
+
+/// The plain Fibonacci statement with every term range checked into the lower half of
+/// the field.
+#[derive(Clone, Debug)]
+pub struct BoundedCircuit<F: FieldExt> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> BoundedCircuit<F> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Self {
+        Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            num_terms,
+        }
+    }
+
+    fn k(&self) -> u32 {
+        let rows = self.num_terms.max(3) - 2 + (LIMBS + 1) * self.num_terms.max(3);
+        k_for_rows(rows.max(1 << LIMB_BITS))
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for BoundedCircuit<F> {
+    type Config = BoundedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BoundedConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.load_table(layouter.namespace(|| "table"))?;
+        let terms = config.assign(
+            layouter.namespace(|| "bounded"),
+            self.elem_1,
+            self.elem_2,
+            self.num_terms,
+        )?;
+        config
+            .fib
+            .expose_public(layouter, &terms[terms.len() - 1], 0)
+    }
+}
+
+/// Proves `F(num_terms)` for the seeds `a, b` with every term in the lower half of the
+/// field.
+pub fn prove_bounded(a: u64, b: u64, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let plain = FibonacciCircuit::<Fp>::new(a, b, num_terms);
+    plain.validate()?;
+    let public_inputs = plain.public_inputs().expect("the seeds are known");
+    let circuit = BoundedCircuit::new(a, b, num_terms);
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_bounded`].
+pub fn verify_bounded(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = BoundedCircuit::<Fp>::new(0, 0, proof.num_terms);
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_terms_stay_in_lower_half() {
+        let circuit = BoundedCircuit::<Fp>::new(1, 1, 80);
+        let instance = FibonacciCircuit::<Fp>::new(1, 1, 80)
+            .public_inputs()
+            .unwrap();
+        MockProver::run(circuit.k(), &circuit, vec![instance])
+            .unwrap()
+            .assert_satisfied();
+
+        // resume the standard sequence at F(364), F(365): F(366) < 2^253 <= F(367)
+        let (mut a, mut b) = (Fp::one(), Fp::one());
+        for _ in 2..365 {
+            (a, b) = (b, a + b);
+        }
+        for (num_terms, satisfied) in [(3, true), (4, false)] {
+            let circuit = BoundedCircuit {
+                elem_1: Value::known(a),
+                elem_2: Value::known(b),
+                num_terms,
+            };
+            let (mut x, mut y) = (a, b);
+            for _ in 2..num_terms {
+                (x, y) = (y, x + y);
+            }
+            let prover = MockProver::run(circuit.k(), &circuit, vec![vec![y]]).unwrap();
+            assert_eq!(prover.verify().is_ok(), satisfied);
+        }
+
+        // -1 is the canonical p - 1, far above the bound
+        let negative = BoundedCircuit {
+            elem_1: Value::known(-Fp::one()),
+            elem_2: Value::known(Fp::one()),
+            num_terms: 3,
+        };
+        let prover = MockProver::run(negative.k(), &negative, vec![vec![Fp::zero()]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,248 @@
+/*
+
+    Fibonacci in two advice columns, for parent circuits short on columns. Each row holds
+    two consecutive terms, and the next row is reached by rotation instead of a third
+    column:
+
+    | elem_1 | elem_2 | q_fib
+    -------------------------
+    |    1   |    1   |   1
+    |    1   |    2   |   1
+    |    2   |    3   |   1
+    |    3   |    5   |   0
+
+    q_fib * (elem_1(next) - elem_2) = 0
+    q_fib * (elem_2(next) - elem_1 - elem_2) = 0
+
+    elem_2 of the last row is the output, so only elem_2 and the instance column need
+    equality.
+
+*/
+
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::fibonacci::Config;
+
+#[derive(Clone, Debug, Copy)]
+pub struct TwoColumnConfig {
+    pub(crate) elem_1: Column<Advice>,
+    pub(crate) elem_2: Column<Advice>,
+    pub(crate) q_fib: Selector,
+    pub(crate) instance: Column<Instance>,
+}
+
+impl TwoColumnConfig {
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+        let elem_1 = cs.advice_column();
+        let elem_2 = cs.advice_column();
+        cs.enable_equality(elem_2);
+        let instance = cs.instance_column();
+        cs.enable_equality(instance);
+        let q_fib = cs.selector();
+
+        cs.create_gate("two-column fibonacci", |virtual_cells| {
+            let q_fib = virtual_cells.query_selector(q_fib);
+            let cur_1 = virtual_cells.query_advice(elem_1, Rotation::cur());
+            let cur_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
+            let next_1 = virtual_cells.query_advice(elem_1, Rotation::next());
+            let next_2 = virtual_cells.query_advice(elem_2, Rotation::next());
+            vec![
+                q_fib.clone() * (next_1 - cur_2.clone()),
+                q_fib * (next_2 - cur_1 - cur_2),
+            ]
+        });
+
+        Self {
+            elem_1,
+            elem_2,
+            q_fib,
+            instance,
+        }
+    }
+
+    /// Lays out the sequence from the seeds in one region and returns the cell holding
+    /// `F(num_terms)` (at least `F(3)`, like the three-column layout).
+    pub fn assign_terms<F: Field>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let steps = num_terms.max(3) - 2;
+        layouter.assign_region(
+            || "two-column sequence",
+            |mut region| {
+                let (mut elem_1, mut elem_2) = (elem_1, elem_2);
+                let mut output = None;
+                for offset in 0..=steps {
+                    if offset < steps {
+                        self.q_fib.enable(&mut region, offset)?;
+                    }
+                    region.assign_advice(|| "elem_1", self.elem_1, offset, || elem_1)?;
+                    output =
+                        Some(region.assign_advice(|| "elem_2", self.elem_2, offset, || elem_2)?);
+                    (elem_1, elem_2) = (elem_2, elem_1 + elem_2);
+                }
+                Ok(output.expect("at least one row"))
+            },
+        )
+    }
+}
+
+/// The chip laid out in either three advice columns ([`Config`]) or two
+/// ([`TwoColumnConfig`]), picked by a column budget.
+#[derive(Clone, Debug, Copy)]
+pub enum BudgetConfig {
+    Three(Config),
+    Two(TwoColumnConfig),
+}
+
+impl BudgetConfig {
+    /// Configures the layout using at most `column_budget` advice columns.
+    ///
+    /// # Panics
+    ///
+    /// If `column_budget` is below 2, the fewest columns either layout needs.
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>, column_budget: usize) -> Self {
+        match column_budget {
+            0 | 1 => panic!(
+                "the chip needs at least 2 advice columns, got a budget of {}",
+                column_budget
+            ),
+            2 => BudgetConfig::Two(TwoColumnConfig::configure(cs)),
+            _ => BudgetConfig::Three(Config::configure(cs)),
+        }
+    }
+
+    /// Lays out the sequence and returns the cell holding `F(num_terms)`.
+    pub fn assign_terms<F: Field>(
+        &self,
+        layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        match self {
+            BudgetConfig::Three(config) => {
+                let terms = config.assign_terms(layouter, elem_1, elem_2, num_terms)?;
+                Ok(terms[terms.len() - 1].clone())
+            }
+            BudgetConfig::Two(config) => config.assign_terms(layouter, elem_1, elem_2, num_terms),
+        }
+    }
+
+    pub fn expose_public<F: Field>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        let instance = match self {
+            BudgetConfig::Three(config) => config.instance,
+            BudgetConfig::Two(config) => config.instance,
+        };
+        layouter.constrain_instance(cell.cell(), instance, row)
+    }
+}
+
+/// The plain Fibonacci statement laid out within a budget of `COLUMN_BUDGET` advice
+/// columns; see [`BudgetConfig::configure`].
+#[derive(Clone, Debug)]
+pub struct BudgetedCircuit<F: Field, const COLUMN_BUDGET: usize> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt, const COLUMN_BUDGET: usize> BudgetedCircuit<F, COLUMN_BUDGET> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Self {
+        const {
+            assert!(
+                COLUMN_BUDGET >= 2,
+                "the chip needs at least 2 advice columns"
+            )
+        };
+        Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            num_terms,
+        }
+    }
+}
+
+impl<F: Field, const COLUMN_BUDGET: usize> Circuit<F> for BudgetedCircuit<F, COLUMN_BUDGET> {
+    type Config = BudgetConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        BudgetConfig::configure(meta, COLUMN_BUDGET)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let output = config.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.elem_1,
+            self.elem_2,
+            self.num_terms,
+        )?;
+        config.expose_public(layouter, &output, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::fibonacci::FibonacciCircuit;
+    use crate::proof::{create_circuit_proof, verify_circuit_proof};
+    use crate::stats::advice_columns;
+
+    #[test]
+    fn test_two_column_budget() {
+        for (a, b, num_terms) in [(1, 1, 10), (2, 5, 20), (3, 7, 3)] {
+            let expected = FibonacciCircuit::<Fp>::new(a, b, num_terms)
+                .public_inputs()
+                .unwrap();
+            let two = BudgetedCircuit::<Fp, 2>::new(a, b, num_terms);
+            let three = BudgetedCircuit::<Fp, 3>::new(a, b, num_terms);
+            for prover in [
+                MockProver::run(5, &two, vec![expected.clone()]).unwrap(),
+                MockProver::run(5, &three, vec![expected.clone()]).unwrap(),
+            ] {
+                prover.assert_satisfied();
+            }
+
+            let mut wrong = expected;
+            wrong[0] += Fp::one();
+            let prover = MockProver::run(5, &two, vec![wrong]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+
+        assert_eq!(advice_columns::<Fp, BudgetedCircuit<Fp, 2>>(), 2);
+        assert_eq!(advice_columns::<Fp, BudgetedCircuit<Fp, 3>>(), 3);
+
+        let two = BudgetedCircuit::<Fp, 2>::new(1, 1, 10);
+
+        let proof = create_circuit_proof(&two, &[Fp::from(55)], 5).unwrap();
+        verify_circuit_proof(&two, &[Fp::from(55)], &proof, 5).unwrap();
+    }
+}
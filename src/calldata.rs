@@ -0,0 +1,69 @@
+//! Calldata layout for on-chain verifiers: every public input as a 32-byte big-endian
+//! word, followed by the raw proof bytes.
+
+use halo2_proofs::pasta::group::ff::PrimeField;
+use halo2_proofs::pasta::Fp;
+
+use crate::error::FibError;
+use crate::proof::FibonacciProof;
+
+/// Size of one encoded public input.
+pub const WORD_SIZE: usize = 32;
+
+/// Lays out `proof` as `public_inputs || proof` calldata.
+pub fn proof_to_calldata(proof: &FibonacciProof) -> Vec<u8> {
+    let mut calldata =
+        Vec::with_capacity(proof.public_inputs.len() * WORD_SIZE + proof.proof.len());
+    for input in &proof.public_inputs {
+        let mut word = input.to_repr();
+        // field elements are little-endian; EVM words are big-endian
+        word.reverse();
+        calldata.extend_from_slice(&word);
+    }
+    calldata.extend_from_slice(&proof.proof);
+    calldata
+}
+
+/// Decodes the first `count` public inputs from calldata built by [`proof_to_calldata`].
+pub fn calldata_public_inputs(calldata: &[u8], count: usize) -> Result<Vec<Fp>, FibError> {
+    if calldata.len() < count * WORD_SIZE {
+        return Err(FibError::BadPublicInputs(format!(
+            "calldata holds {} bytes, too short for {} public inputs",
+            calldata.len(),
+            count
+        )));
+    }
+    calldata
+        .chunks_exact(WORD_SIZE)
+        .take(count)
+        .map(|word| {
+            let mut repr = [0u8; WORD_SIZE];
+            repr.copy_from_slice(word);
+            repr.reverse();
+            Option::from(Fp::from_repr(repr)).ok_or_else(|| {
+                FibError::BadPublicInputs("public input is not a canonical field element".into())
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fibonacci::FibonacciCircuit;
+    use crate::proof::prove;
+
+    use super::*;
+
+    #[test]
+    fn test_calldata_layout() {
+        let proof = prove(&FibonacciCircuit::<Fp>::new(1, 1, 10), 5).unwrap();
+        let calldata = proof_to_calldata(&proof);
+        assert_eq!(calldata.len(), WORD_SIZE + proof.proof.len());
+        assert_eq!(calldata[WORD_SIZE - 1], 55);
+        assert_eq!(&calldata[WORD_SIZE..], &proof.proof[..]);
+
+        let inputs = calldata_public_inputs(&calldata, 1).unwrap();
+        assert_eq!(inputs, vec![Fp::from(55)]);
+        assert!(calldata_public_inputs(&calldata[..16], 1).is_err());
+    }
+}
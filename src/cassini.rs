@@ -0,0 +1,240 @@
+/*
+
+    F(n + 1) * F(n - 1) - F(n)^2 = (-1)^n (Cassini's identity)
+
+    The sequence is laid out up to F(n + 1), then one extra row copies the last three
+    terms next to the expected sign, a fixed value chosen by the parity of n:
+
+    | elem_1 | elem_2 |  elem_3  | expected | q_cassini
+    ---------------------------------------------------
+    | F(n-1) |  F(n)  | F(n + 1) |  (-1)^n  |     1
+
+    q_cassini * (elem_3 * elem_1 - elem_2^2 - expected) = 0
+
+    Both seeds are pinned to 1, since other seeds shifted along the sequence (2, 3 say)
+    satisfy the identity too. F(n) is the public input; n itself is fixed by the circuit
+    shape.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct CassiniConfig {
+    fib: Config,
+    expected: Column<Fixed>,
+    q_cassini: Selector,
+}
+
+impl CassiniConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let expected = cs.fixed_column();
+        // pins the seeds
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let q_cassini = cs.selector();
+
+        cs.create_gate("cassini", |virtual_cells| {
+            let q_cassini = virtual_cells.query_selector(q_cassini);
+            let prev = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let cur = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let next = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let expected = virtual_cells.query_fixed(expected, Rotation::cur());
+            vec![q_cassini * (next * prev - cur.clone() * cur - expected)]
+        });
+
+        Self {
+            fib,
+            expected,
+            q_cassini,
+        }
+    }
+}
+
+/// Circuit proving Cassini's identity at index `n` for the standard `1, 1` sequence,
+/// exposing `F(n)`.
+#[derive(Clone, Debug)]
+pub struct CassiniCircuit {
+    n: usize,
+    // the witnessed seeds; only a test hands in anything but 1, 1
+    seeds: (u64, u64),
+    witness: bool,
+}
+
+impl CassiniCircuit {
+    pub fn new(n: usize) -> Result<Self, FibError> {
+        if n < 2 {
+            return Err(FibError::BadPublicInputs(format!(
+                "Cassini's identity needs n >= 2, got {}",
+                n
+            )));
+        }
+        Ok(Self {
+            n,
+            seeds: (1, 1),
+            witness: true,
+        })
+    }
+
+    /// `(-1)^n` in the field: `1` for even `n`, `p - 1` for odd `n`.
+    pub fn expected<F: FieldExt>(&self) -> F {
+        if self.n.is_multiple_of(2) {
+            F::one()
+        } else {
+            -F::one()
+        }
+    }
+
+    /// `F(n)`, the public input.
+    pub fn output(&self) -> Fp {
+        FibonacciCircuit::<Fp>::new(1, 1, self.n)
+            .output()
+            .expect("seeds are known")
+    }
+
+    fn k(&self) -> u32 {
+        // the sequence rows up to F(n + 1) plus the identity row
+        k_for_rows(self.n)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for CassiniCircuit {
+    type Config = CassiniConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        CassiniConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = |x: u64| {
+            if self.witness {
+                Value::known(F::from(x))
+            } else {
+                Value::unknown()
+            }
+        };
+        let terms = config.fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            witness(self.seeds.0),
+            witness(self.seeds.1),
+            self.n + 1,
+        )?;
+        // terms[i] holds F(i + 1)
+        let term = |i: usize| &terms[i - 1];
+
+        let f_n = layouter.assign_region(
+            || "cassini",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::one())?;
+
+                let offset = 0;
+                config.q_cassini.enable(&mut region, offset)?;
+                term(self.n - 1).copy_advice(
+                    || "F(n - 1)",
+                    &mut region,
+                    config.fib.elem_1,
+                    offset,
+                )?;
+                let f_n =
+                    term(self.n).copy_advice(|| "F(n)", &mut region, config.fib.elem_2, offset)?;
+                term(self.n + 1).copy_advice(
+                    || "F(n + 1)",
+                    &mut region,
+                    config.fib.elem_3,
+                    offset,
+                )?;
+                region.assign_fixed(
+                    || "(-1)^n",
+                    config.expected,
+                    offset,
+                    || Value::known(self.expected::<F>()),
+                )?;
+                Ok(f_n)
+            },
+        )?;
+
+        config.fib.expose_public(layouter, &f_n, 0)
+    }
+}
+
+/// Proves Cassini's identity `F(n + 1) * F(n - 1) - F(n)^2 = (-1)^n`.
+pub fn prove_cassini(n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = CassiniCircuit::new(n)?;
+    let public_inputs = vec![circuit.output()];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_cassini`].
+pub fn verify_cassini(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = CassiniCircuit::new(proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_cassini_even() {
+        // F(11) * F(9) - F(10)^2 = 89 * 34 - 55^2 = 1
+        let proof = prove_cassini(10).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(55)]);
+        verify_cassini(&proof).unwrap();
+        assert_eq!(CassiniCircuit::new(10).unwrap().expected::<Fp>(), Fp::one());
+    }
+
+    #[test]
+    fn test_cassini_odd() {
+        // F(10) * F(8) - F(9)^2 = 55 * 21 - 34^2 = -1
+        let circuit = CassiniCircuit::new(9).unwrap();
+        assert_eq!(circuit.expected::<Fp>(), -Fp::one());
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(34)]]).unwrap();
+        prover.assert_satisfied();
+
+        let proof = prove_cassini(9).unwrap();
+        verify_cassini(&proof).unwrap();
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 3 run two terms ahead, so the identity holds with F(10) = 144
+        let circuit = CassiniCircuit {
+            seeds: (2, 3),
+            ..CassiniCircuit::new(10).unwrap()
+        };
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(144)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
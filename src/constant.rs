@@ -0,0 +1,113 @@
+//! Baking the expected output into the circuit as a constant, so the statement is fully
+//! determined by the verifying key and the instance column stays empty.
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner};
+use halo2_proofs::plonk::*;
+
+use crate::fibonacci::{Config, Equality, FibonacciCircuit};
+
+#[derive(Clone, Debug)]
+pub struct ConstantConfig {
+    fib: Config,
+}
+
+impl ConstantConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        // nothing is exposed, so the instance column stays out of the permutation
+        let fib = Config::configure_with_equality(
+            cs,
+            Equality {
+                instance: false,
+                ..Equality::ALL
+            },
+        );
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        Self { fib }
+    }
+}
+
+/// A [`FibonacciCircuit`] whose output is constrained to the constant `expected` instead
+/// of an instance value; built by [`FibonacciCircuit::with_constant_output`]. The exposure
+/// flags of the inner circuit are ignored.
+#[derive(Clone, Debug)]
+pub struct ConstantOutputCircuit<F: FieldExt> {
+    circuit: FibonacciCircuit<F>,
+    expected: F,
+}
+
+impl<F: FieldExt> ConstantOutputCircuit<F> {
+    pub(crate) fn new(circuit: FibonacciCircuit<F>, expected: F) -> Self {
+        Self { circuit, expected }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ConstantOutputCircuit<F> {
+    type Config = ConstantConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ConstantConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (elem_1, elem_2) = self.circuit.seeds();
+        let terms = config.fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            elem_1,
+            elem_2,
+            self.circuit.num_terms(),
+        )?;
+        let output = &terms[terms.len() - 1];
+
+        layouter.assign_region(
+            || "constant output",
+            |mut region| {
+                let output = output.copy_advice(|| "F(n)", &mut region, config.fib.elem_1, 0)?;
+                region.constrain_constant(output.cell(), self.expected)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::stats::permutation_columns;
+
+    #[test]
+    fn test_baked_output() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10).with_constant_output(Fp::from(55));
+        let prover = MockProver::run(5, &circuit, vec![vec![]]).unwrap();
+        prover.assert_satisfied();
+
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10).with_constant_output(Fp::from(56));
+        let prover = MockProver::run(5, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_instance_left_out_of_permutation() {
+        let plain = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let baked = plain.clone().with_constant_output(Fp::from(55));
+        // the constants column takes the instance column's place
+        assert_eq!(permutation_columns(&plain, 5), 4);
+        assert_eq!(permutation_columns(&baked, 5), 4);
+    }
+}
@@ -0,0 +1,195 @@
+/*
+
+    Fibonacci with a single copy lane: rows are chained by a gate instead of copy
+    constraints, so only elem_3 takes part in the permutation argument.
+
+    | elem_1 | elem_2 | elem_3 | q_fib | q_shift
+    ---------------------------------------------
+    |    1   |    1   |    2   |   1   |    1
+    |    1   |    2   |    3   |   1   |    1
+    |    2   |    3   |    5   |   1   |    0
+
+    q_fib * (elem_1 + elem_2 - elem_3) = 0
+    q_shift * (elem_1(next) - elem_2) = 0
+    q_shift * (elem_2(next) - elem_3) = 0
+
+*/
+
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::{AssignedCell, Layouter, Region, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+#[derive(Clone, Debug, Copy)]
+pub struct CopyLaneConfig {
+    pub(crate) elem_1: Column<Advice>,
+    pub(crate) elem_2: Column<Advice>,
+    pub(crate) elem_3: Column<Advice>,
+    pub(crate) q_fib: Selector,
+    pub(crate) q_shift: Selector,
+    pub(crate) instance: Column<Instance>,
+}
+
+impl CopyLaneConfig {
+    /// Like [`Config::configure`](crate::Config::configure), but only `elem_3` (the copy
+    /// lane) and the instance column have equality enabled.
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+        let elem_1 = cs.advice_column();
+        let elem_2 = cs.advice_column();
+        let elem_3 = cs.advice_column();
+        cs.enable_equality(elem_3);
+        let instance = cs.instance_column();
+        cs.enable_equality(instance);
+        let q_fib = cs.selector();
+        let q_shift = cs.selector();
+
+        cs.create_gate("fibonacci", |virtual_cells| {
+            let q_fib = virtual_cells.query_selector(q_fib);
+            let elem_1 = virtual_cells.query_advice(elem_1, Rotation::cur());
+            let elem_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
+            let elem_3 = virtual_cells.query_advice(elem_3, Rotation::cur());
+            vec![q_fib * (elem_1 + elem_2 - elem_3)]
+        });
+
+        cs.create_gate("shift", |virtual_cells| {
+            let q_shift = virtual_cells.query_selector(q_shift);
+            let next_elem_1 = virtual_cells.query_advice(elem_1, Rotation::next());
+            let next_elem_2 = virtual_cells.query_advice(elem_2, Rotation::next());
+            let elem_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
+            let elem_3 = virtual_cells.query_advice(elem_3, Rotation::cur());
+            vec![
+                q_shift.clone() * (next_elem_1 - elem_2),
+                q_shift * (next_elem_2 - elem_3),
+            ]
+        });
+
+        Self {
+            elem_1,
+            elem_2,
+            elem_3,
+            q_fib,
+            q_shift,
+            instance,
+        }
+    }
+
+    /// Lays out `rows` chained rows from offset 0 of `region` and returns the `elem_3`
+    /// cell of the last one.
+    pub(crate) fn assign_rows<F: Field>(
+        &self,
+        region: &mut Region<'_, F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        rows: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let [_, _, output] = self.assign_rows_with_seeds(region, elem_1, elem_2, rows)?;
+        Ok(output)
+    }
+
+    /// Like [`Self::assign_rows`], also returning the two seed cells of the first row:
+    /// `[elem_1, elem_2, output]`.
+    pub(crate) fn assign_rows_with_seeds<F: Field>(
+        &self,
+        region: &mut Region<'_, F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        rows: usize,
+    ) -> Result<[AssignedCell<F, F>; 3], Error> {
+        let (mut elem_1, mut elem_2) = (elem_1, elem_2);
+        let mut seeds = None;
+        let mut output = None;
+        for offset in 0..rows {
+            self.q_fib.enable(region, offset)?;
+            if offset + 1 < rows {
+                self.q_shift.enable(region, offset)?;
+            }
+            let cell_1 = region.assign_advice(|| "elem_1", self.elem_1, offset, || elem_1)?;
+            let cell_2 = region.assign_advice(|| "elem_2", self.elem_2, offset, || elem_2)?;
+            if offset == 0 {
+                seeds = Some((cell_1, cell_2));
+            }
+            let elem_3 = elem_1 + elem_2;
+            output = Some(region.assign_advice(|| "elem_3", self.elem_3, offset, || elem_3)?);
+            (elem_1, elem_2) = (elem_2, elem_3);
+        }
+        let (seed_1, seed_2) = seeds.expect("at least one row");
+        Ok([seed_1, seed_2, output.expect("at least one row")])
+    }
+}
+
+/// The plain Fibonacci statement (the `num_terms`-th term is the public input) laid out
+/// with a single copy lane, trading two copy constraints per row for one extra gate.
+#[derive(Clone, Debug)]
+pub struct CopyLaneCircuit<F: Field> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> CopyLaneCircuit<F> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Self {
+        Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            num_terms,
+        }
+    }
+}
+
+impl<F: Field> Circuit<F> for CopyLaneCircuit<F> {
+    type Config = CopyLaneConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        CopyLaneConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let rows = self.num_terms.saturating_sub(2).max(1);
+        let output = layouter.assign_region(
+            || "sequence",
+            |mut region| config.assign_rows(&mut region, self.elem_1, self.elem_2, rows),
+        )?;
+        layouter.constrain_instance(output.cell(), config.instance, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+    use crate::fibonacci::FibonacciCircuit;
+    use crate::stats::permutation_columns;
+
+    #[test]
+    fn test_copy_lane() {
+        let circuit = CopyLaneCircuit::<Fp>::new(1, 1, 10);
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+        prover.assert_satisfied();
+
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(56)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_fewer_permutation_columns() {
+        let plain = permutation_columns(&FibonacciCircuit::<Fp>::new(1, 1, 10), 5);
+        let lane = permutation_columns(&CopyLaneCircuit::<Fp>::new(1, 1, 10), 5);
+        assert_eq!((plain, lane), (4, 2));
+    }
+}
@@ -0,0 +1,218 @@
+/*
+
+    F(n) mod several small moduli at once: one reduced sequence per modulus (see the
+    modular layout), stacked in the same columns and sharing one residue table. The final
+    residue of each is exposed, in the order of the moduli:
+
+    | instance
+    ------------
+    | F(n) mod m_1
+    | F(n) mod m_2
+    | ...
+
+    Both seeds of every sequence are pinned to 1. If the moduli are pairwise coprime and
+    F(n) is below their product, the residues determine F(n) by the Chinese remainder
+    theorem.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+
+use crate::error::FibError;
+use crate::fibonacci::MAX_SUPPORTED_K;
+use crate::modular::{ModularCircuit, ModularConfig};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::fib_mod;
+
+/// Circuit proving `F(n) mod m` for every `m` in `moduli`, with the standard `1, 1` seeds.
+#[derive(Clone, Debug)]
+pub struct CrtCircuit {
+    num_terms: usize,
+    moduli: Vec<u64>,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl CrtCircuit {
+    pub fn new(num_terms: usize, moduli: &[u64]) -> Result<Self, FibError> {
+        if moduli.is_empty() {
+            return Err(FibError::BadPublicInputs(
+                "need at least one modulus".to_string(),
+            ));
+        }
+        // the per-modulus checks are the ones of the single-modulus circuit
+        for &modulus in moduli {
+            ModularCircuit::new(1, 1, num_terms, modulus)?;
+        }
+        let circuit = Self {
+            num_terms,
+            moduli: moduli.to_vec(),
+            seed: 1,
+            witness: true,
+        };
+        // the stacked sequences can outgrow what each fits on its own
+        if circuit.k() > MAX_SUPPORTED_K {
+            return Err(FibError::BadPublicInputs(format!(
+                "{} terms mod {} moduli need k = {}, above the supported {}",
+                num_terms,
+                moduli.len(),
+                circuit.k(),
+                MAX_SUPPORTED_K
+            )));
+        }
+        Ok(circuit)
+    }
+
+    /// The reduced sequence for each modulus.
+    fn sequences(&self) -> Vec<Vec<u64>> {
+        self.moduli
+            .iter()
+            .map(|&modulus| fib_mod(self.seed, self.seed, self.num_terms, modulus))
+            .collect()
+    }
+
+    /// `F(n) mod m` for each modulus, which are also the public inputs.
+    pub fn residues(&self) -> Vec<u64> {
+        self.sequences()
+            .into_iter()
+            .map(|sequence| sequence[self.num_terms - 1])
+            .collect()
+    }
+
+    fn k(&self) -> u32 {
+        let rows = self.moduli.len() * (self.num_terms - 2);
+        let table = 1 + self.moduli.iter().sum::<u64>() as usize;
+        k_for_rows(rows.max(table + 1))
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for CrtCircuit {
+    type Config = ModularConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..self.clone()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ModularConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.load_table(layouter.namespace(|| "table"), &self.moduli)?;
+
+        for (row, (sequence, &modulus)) in self.sequences().iter().zip(&self.moduli).enumerate() {
+            let residues = if self.witness {
+                Value::known(sequence.as_slice())
+            } else {
+                Value::unknown()
+            };
+            let cells = config.assign_standard_sequence(
+                layouter.namespace(|| "sequence"),
+                residues,
+                self.num_terms,
+                modulus,
+            )?;
+            config.expose_public(
+                layouter.namespace(|| "residue"),
+                &cells[cells.len() - 1],
+                row,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Proves `F(n) mod m` for every `m` in `moduli`.
+pub fn prove_crt(n: usize, moduli: &[u64]) -> Result<FibonacciProof, FibError> {
+    let circuit = CrtCircuit::new(n, moduli)?;
+    let public_inputs: Vec<Fp> = circuit.residues().into_iter().map(Fp::from).collect();
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_crt`] for the same moduli.
+pub fn verify_crt(moduli: &[u64], proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = CrtCircuit::new(proof.num_terms, moduli)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::group::ff::PrimeField;
+
+    use super::*;
+    use crate::reference::fib_u64;
+
+    /// The `x < product(moduli)` with `x = r_i mod m_i`, for pairwise coprime moduli.
+    fn reconstruct(residues: &[u64], moduli: &[u64]) -> u64 {
+        let product: u64 = moduli.iter().product();
+        (0..product)
+            .find(|x| residues.iter().zip(moduli).all(|(r, m)| x % m == *r))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_crt_reconstruction() {
+        let moduli = [7, 11, 13];
+        // F(16) = 987 < 7 * 11 * 13 = 1001
+        let proof = prove_crt(16, &moduli).unwrap();
+        verify_crt(&moduli, &proof).unwrap();
+
+        let residues: Vec<u64> = proof
+            .public_inputs
+            .iter()
+            .map(|r| u64::from_le_bytes(r.to_repr()[..8].try_into().unwrap()))
+            .collect();
+        assert_eq!(residues, vec![987 % 7, 987 % 11, 987 % 13]);
+        assert_eq!(reconstruct(&residues, &moduli), fib_u64(16));
+
+        let mut forged = proof;
+        forged.public_inputs[1] += Fp::one();
+        assert!(matches!(
+            verify_crt(&moduli, &forged),
+            Err(FibError::Verify)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_circuit_rejected() {
+        // each sequence fits on its own, but not the three stacked
+        assert!(CrtCircuit::new(1 << 30, &[7]).is_ok());
+        assert!(matches!(
+            CrtCircuit::new(1 << 30, &[7, 11, 13]),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 2 reduce to twice every residue
+        let circuit = CrtCircuit {
+            seed: 2,
+            ..CrtCircuit::new(16, &[7, 11, 13]).unwrap()
+        };
+        let instance = circuit.residues().into_iter().map(Fp::from).collect();
+        let prover = MockProver::run(circuit.k(), &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+/*
+
+    Seeds derived from a public value h (e.g. a hash or commitment): a = h, b = h + 1.
+    After the sequence, one row copies the seeds back and checks the derivation:
+
+    | elem_1 | elem_2 | q_derive | instance
+    ---------------------------------------
+    |    a   |    b   |    1     |    h
+    |        |        |          |   F(n)
+
+    q_derive * (elem_2 - elem_1 - 1) = 0
+
+    and a is copied to the instance at row 0.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{min_k, Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct DerivedSeedConfig {
+    fib: Config,
+    q_derive: Selector,
+}
+
+impl DerivedSeedConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let q_derive = cs.selector();
+
+        cs.create_gate("seed derivation", |virtual_cells| {
+            let q_derive = virtual_cells.query_selector(q_derive);
+            let a = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let b = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            vec![q_derive * (b - a - Expression::Constant(F::one()))]
+        });
+
+        Self { fib, q_derive }
+    }
+}
+
+/// Circuit proving the `num_terms`-th term of the sequence seeded with `h, h + 1`, exposing
+/// `[h, F(n)]`.
+#[derive(Clone, Debug)]
+pub struct DerivedSeedCircuit<F: FieldExt> {
+    h: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> DerivedSeedCircuit<F> {
+    pub fn new(h: F, num_terms: usize) -> Self {
+        Self {
+            h: Value::known(h),
+            num_terms,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for DerivedSeedCircuit<F> {
+    type Config = DerivedSeedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            h: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        DerivedSeedConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.h,
+            self.h + Value::known(F::one()),
+            self.num_terms,
+        )?;
+
+        layouter.assign_region(
+            || "seed derivation",
+            |mut region| {
+                let offset = 0;
+                config.q_derive.enable(&mut region, offset)?;
+                terms[0].copy_advice(|| "a", &mut region, fib.elem_1, offset)?;
+                terms[1].copy_advice(|| "b", &mut region, fib.elem_2, offset)?;
+                Ok(())
+            },
+        )?;
+
+        fib.expose_public(layouter.namespace(|| "h"), &terms[0], 0)?;
+        fib.expose_public(layouter.namespace(|| "output"), &terms[terms.len() - 1], 1)
+    }
+}
+
+/// Proves the `num_terms`-th term of the sequence seeded with `h, h + 1`.
+pub fn prove_from_hash(h: Fp, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let sequence = FibonacciCircuit::from_seeds(h, h + Fp::one(), num_terms);
+    sequence.validate()?;
+    let output = sequence.output().expect("seeds are known");
+    let circuit = DerivedSeedCircuit::new(h, num_terms);
+    let public_inputs = vec![h, output];
+    // the sequence rows plus the derivation row
+    let k = min_k(num_terms + 1);
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_from_hash`], returning the proven output.
+pub fn verify_from_hash(proof: &FibonacciProof) -> Result<Fp, FibError> {
+    let circuit = DerivedSeedCircuit::new(Fp::zero(), proof.num_terms);
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)?;
+    Ok(proof.public_inputs[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeds_derived_from_hash() {
+        // 5, 6, 11, 17, 28, 45
+        let proof = prove_from_hash(Fp::from(5), 6).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(5), Fp::from(45)]);
+        assert_eq!(verify_from_hash(&proof).unwrap(), Fp::from(45));
+
+        let mut other_hash = proof;
+        other_hash.public_inputs[0] = Fp::from(6);
+        assert!(matches!(
+            verify_from_hash(&other_hash),
+            Err(FibError::Verify)
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+/*
+
+    F(m) | F(n) whenever m | n
+
+    The sequence is laid out up to n with both seeds pinned to 1. The quotient is
+    decomposed into 64 bits as in the parity layout, then one extra row copies F(m), the
+    quotient and F(n):
+
+    | elem_1 |    elem_2   | elem_3 | q_bits | q_div
+    ------------------------------------------------
+    |  b_63  |    acc_0    |        |   1    |   0
+    |  ...   |     ...     |        |  ...   |  ...
+    |  b_0   |    acc_63   |        |   0    |   0
+    |  F(m)  | F(n) / F(m) |  F(n)  |   0    |   1
+
+    q_div * (elem_1 * elem_2 - elem_3) = 0
+
+    acc_63 is the quotient. Both F(m) and F(n) fit in a u64, as does the quotient by the
+    decomposition, so F(m) * quotient stays far below the modulus and the division is exact
+    over the integers, not just in the field.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::parity::{ParityConfig, BITS};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::{fib_u64, MAX_U64_INDEX};
+
+#[derive(Clone, Debug)]
+pub struct DividesConfig {
+    bits: ParityConfig,
+    q_div: Selector,
+}
+
+impl DividesConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let bits = ParityConfig::configure(cs);
+        let fib = bits.fib;
+        // pins the seeds
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let q_div = cs.selector();
+
+        cs.create_gate("divides", |virtual_cells| {
+            let q_div = virtual_cells.query_selector(q_div);
+            let divisor = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let quotient = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let dividend = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+
+            vec![q_div * (divisor * quotient - dividend)]
+        });
+
+        Self { bits, q_div }
+    }
+}
+
+/// Circuit proving `F(m) | F(n)` for the standard `1, 1` seeds, exposing `[F(m), F(n)]`
+/// as the public inputs.
+#[derive(Clone, Debug)]
+pub struct DividesCircuit {
+    m: usize,
+    n: usize,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl DividesCircuit {
+    pub fn new(m: usize, n: usize) -> Result<Self, FibError> {
+        if !(1..=MAX_U64_INDEX).contains(&m) || !(1..=MAX_U64_INDEX).contains(&n) {
+            return Err(FibError::BadPublicInputs(format!(
+                "indices must be in 1..={}, got ({}, {})",
+                MAX_U64_INDEX, m, n
+            )));
+        }
+        if !n.is_multiple_of(m) {
+            return Err(FibError::BadPublicInputs(format!(
+                "{} does not divide {}, so F({}) need not divide F({})",
+                m, n, m, n
+            )));
+        }
+        Ok(Self {
+            m,
+            n,
+            seed: 1,
+            witness: true,
+        })
+    }
+
+    fn num_terms(&self) -> usize {
+        self.n.max(3)
+    }
+
+    /// The sequence rows, the decomposition of the quotient and the quotient row.
+    fn k(&self) -> u32 {
+        k_for_rows(self.num_terms() - 2 + BITS + 1)
+    }
+
+    /// `[F(m), F(n)]`, the public inputs.
+    pub fn public_inputs(&self) -> Vec<u64> {
+        vec![fib_u64(self.m), fib_u64(self.n)]
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for DividesCircuit {
+    type Config = DividesConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        DividesConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.bits.fib;
+        let witness = |x: u64| {
+            if self.witness {
+                Value::known(x)
+            } else {
+                Value::unknown()
+            }
+        };
+        let seed = witness(self.seed).map(F::from);
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            seed,
+            seed,
+            self.num_terms(),
+        )?;
+        let term = |i: usize| &terms[i - 1];
+
+        let quotient = witness(fib_u64(self.n) / fib_u64(self.m));
+
+        let (divisor, dividend) = layouter.assign_region(
+            || "divides",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::one())?;
+
+                let cells = config.bits.assign_bits(&mut region, quotient)?;
+                let (_, quotient) = cells.last().expect("at least one bit");
+
+                let offset = BITS;
+                config.q_div.enable(&mut region, offset)?;
+                let divisor =
+                    term(self.m).copy_advice(|| "F(m)", &mut region, fib.elem_1, offset)?;
+                quotient.copy_advice(|| "F(n) / F(m)", &mut region, fib.elem_2, offset)?;
+                let dividend =
+                    term(self.n).copy_advice(|| "F(n)", &mut region, fib.elem_3, offset)?;
+
+                Ok((divisor, dividend))
+            },
+        )?;
+
+        fib.expose_public(layouter.namespace(|| "F(m)"), &divisor, 0)?;
+        fib.expose_public(layouter.namespace(|| "F(n)"), &dividend, 1)
+    }
+}
+
+/// Proves that `F(m)` divides `F(n)`; requires `m | n`.
+pub fn prove_divides(m: usize, n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = DividesCircuit::new(m, n)?;
+    let public_inputs: Vec<Fp> = circuit.public_inputs().into_iter().map(Fp::from).collect();
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: circuit.num_terms(),
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_divides`] for the same `m` and `n`.
+pub fn verify_divides(m: usize, n: usize, proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = DividesCircuit::new(m, n)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_divides() {
+        // F(3) = 2 divides F(6) = 8
+        let proof = prove_divides(3, 6).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(2), Fp::from(8)]);
+        verify_divides(3, 6, &proof).unwrap();
+
+        let circuit = DividesCircuit::new(3, 6).unwrap();
+        let prover =
+            MockProver::run(proof.k, &circuit, vec![vec![Fp::from(3), Fp::from(8)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 2 double every term, so F(3) = 4 and F(6) = 16 with the same quotient
+        let circuit = DividesCircuit {
+            seed: 2,
+            ..DividesCircuit::new(3, 6).unwrap()
+        };
+        let instance = vec![Fp::from(4), Fp::from(16)];
+        let prover = MockProver::run(circuit.k(), &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_non_divisible_indices_rejected() {
+        assert!(matches!(
+            prove_divides(4, 6),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+use halo2_proofs::dev::VerifyFailure;
+use halo2_proofs::plonk;
+
+/// Errors that can occur while building, proving or checking a Fibonacci circuit.
+#[derive(Debug)]
+pub enum FibError {
+    /// The seeds or public inputs describe a statement the circuit refuses to prove.
+    BadPublicInputs(String),
+    /// halo2 failed while synthesizing the circuit.
+    Synthesis(plonk::Error),
+    /// The proof does not verify against the statement.
+    Verify,
+    /// `MockProver` found constraints the witness does not satisfy.
+    Unsatisfied(Vec<VerifyFailure>),
+    /// More terms were requested than fit in the largest supported circuit.
+    TooManyTerms(usize),
+    /// Reading or writing a setup artifact failed.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for FibError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FibError::BadPublicInputs(msg) => write!(f, "bad public inputs: {}", msg),
+            FibError::Synthesis(e) => write!(f, "synthesis failed: {}", e),
+            FibError::Verify => write!(f, "proof verification failed"),
+            FibError::TooManyTerms(num_terms) => write!(
+                f,
+                "{} terms don't fit in a circuit of size 2^{} (at most {})",
+                num_terms,
+                crate::fibonacci::MAX_SUPPORTED_K,
+                crate::fibonacci::max_terms_for_k(crate::fibonacci::MAX_SUPPORTED_K)
+            ),
+            FibError::Io(e) => write!(f, "i/o failed: {}", e),
+            FibError::Unsatisfied(failures) => {
+                write!(f, "{} unsatisfied constraint(s)", failures.len())?;
+                for failure in failures {
+                    write!(f, "\n- {}", failure)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FibError {}
+
+/// An actionable hint for a `MockProver` failure, naming the likely mistake in the layout
+/// rather than just the failing constraint.
+pub fn describe_failure(failure: &VerifyFailure) -> String {
+    match failure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint,
+            location,
+            ..
+        } => {
+            if constraint.to_string().ends_with("('fibonacci')") {
+                format!(
+                    "a computed elem_3 doesn't equal elem_1 + elem_2 {}; check your assign logic",
+                    location
+                )
+            } else {
+                format!(
+                    "{} doesn't hold {}; check the values assigned there",
+                    constraint, location
+                )
+            }
+        }
+        VerifyFailure::Permutation { column, location } => {
+            if column.to_string().contains("Instance") {
+                format!(
+                    "the public input {} doesn't match the cell exposed to it; check the \
+                     order, length and values of the instance vector",
+                    location
+                )
+            } else {
+                format!(
+                    "a cell in {} {} differs from the cell it is copied from; check the \
+                     copy_advice and constrain_equal calls",
+                    column, location
+                )
+            }
+        }
+        VerifyFailure::CellNotAssigned {
+            gate,
+            region,
+            column,
+            offset,
+            ..
+        } => format!(
+            "{} reads {:?} at offset {} of {}, which is never assigned; assign the cell \
+             or don't enable the selector there",
+            gate, column, offset, region
+        ),
+        VerifyFailure::ConstraintPoisoned { constraint } => format!(
+            "{} is active on the unusable rows at the end of the circuit; gate it with a \
+             selector",
+            constraint
+        ),
+        VerifyFailure::Lookup {
+            lookup_index,
+            location,
+        } => format!(
+            "the input of lookup {} {} isn't in its table; check the table contents and \
+             that the input is zero wherever the lookup's selector is off",
+            lookup_index, location
+        ),
+    }
+}
+
+impl From<plonk::Error> for FibError {
+    fn from(e: plonk::Error) -> Self {
+        FibError::Synthesis(e)
+    }
+}
+
+impl From<std::io::Error> for FibError {
+    fn from(e: std::io::Error) -> Self {
+        FibError::Io(e)
+    }
+}
@@ -14,51 +14,162 @@
 */
 
 // Halo2プルーフシステムとその他必要なクレートからの要素をインポート
-use halo2_proofs::arithmetic::Field;
-use halo2_proofs::circuit::{AssignedCell, Layouter, Value};
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::{AssignedCell, Layouter, Region, SimpleFloorPlanner, Value};
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::pasta::group::ff::PrimeField;
+use halo2_proofs::pasta::Fp;
 use halo2_proofs::plonk::*;
 use halo2_proofs::poly::Rotation;
+use rand_core::RngCore;
+
+use crate::constant::ConstantOutputCircuit;
+use crate::error::FibError;
+use crate::lazy::LazyCircuit;
+use crate::observer::ObservedCircuit;
+use crate::padded::PaddedCircuit;
+use crate::reference::FibIter;
+use crate::relation::SeedRelationCircuit;
+use crate::stats::CircuitStats;
+use crate::transform::TransformedCircuit;
 
 // Config構造体を定義。これは、回路の構成を保持します。
 #[derive(Clone, Debug, Copy)]
-struct Config {
-    elem_1: Column<Advice>,     // 最初のフィボナッチ数を格納するadvice column
-    elem_2: Column<Advice>,     // 2番目のフィボナッチ数を格納するadvice column
-    elem_3: Column<Advice>,     // 計算される数を格納するadvice column
-    q_fib: Selector,            // 計算の適用を制御するselector
-    instance: Column<Instance>, // public inputを格納するinstance column
+pub struct Config {
+    pub(crate) elem_1: Column<Advice>, // 最初のフィボナッチ数を格納するadvice column
+    pub(crate) elem_2: Column<Advice>, // 2番目のフィボナッチ数を格納するadvice column
+    pub(crate) elem_3: Column<Advice>, // 計算される数を格納するadvice column
+    pub(crate) q_fib: Selector,        // 計算の適用を制御するselector
+    pub(crate) instance: Column<Instance>, // public inputを格納するinstance column
+}
+
+/// Which of the chip's columns get equality enabled, i.e. take part in the permutation
+/// argument. Every column costs permutation work, so a layout that never copies into or
+/// out of a column can leave it off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Equality {
+    pub elem_1: bool,
+    pub elem_2: bool,
+    pub elem_3: bool,
+    pub instance: bool,
+}
+
+impl Equality {
+    /// Equality on every column, as the standard layout needs.
+    pub const ALL: Self = Self {
+        elem_1: true,
+        elem_2: true,
+        elem_3: true,
+        instance: true,
+    };
+}
+
+impl Default for Equality {
+    fn default() -> Self {
+        Self::ALL
+    }
 }
 
 impl Config {
     // Configのconfigureメソッドを定義。これは、回路の設定を行う
-    fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+        Self::configure_named(cs, "fibonacci")
+    }
+
+    /// Like [`Self::configure`], enabling equality only on the columns `equality` selects.
+    /// Copying into or out of a column left off fails at synthesis.
+    pub fn configure_with_equality<F: Field>(
+        cs: &mut ConstraintSystem<F>,
+        equality: Equality,
+    ) -> Self {
+        Self::configure_with(cs, "fibonacci", equality, Self::fibonacci_gate)
+    }
+
+    /// Like [`Self::configure`], but names the gate `gate_name` so several chips in one
+    /// circuit can be told apart in `VerifyFailure` messages.
+    pub fn configure_named<F: Field>(
+        cs: &mut ConstraintSystem<F>,
+        gate_name: &'static str,
+    ) -> Self {
+        Self::configure_with(cs, gate_name, Equality::ALL, Self::fibonacci_gate)
+    }
+
+    pub(crate) fn fibonacci_gate<F: Field>(
+        virtual_cells: &mut VirtualCells<F>,
+        elem_1: Column<Advice>,
+        elem_2: Column<Advice>,
+        elem_3: Column<Advice>,
+        q_fib: Selector,
+    ) -> Expression<F> {
+        // セレクタと各advice columnの現在の値を問い合わせる
+        let q_fib = virtual_cells.query_selector(q_fib);
+        let elem_1 = virtual_cells.query_advice(elem_1, Rotation::cur());
+        let elem_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
+        let elem_3 = virtual_cells.query_advice(elem_3, Rotation::cur());
+
+        // フィボナッチ数列の特定の性質を検証する制約を定義します。
+        // elem_1 + elem_2 - elem_3 が0となるようにする　-> elem_3 = elem_1 + elem_2 を保証する
+        q_fib * (elem_1 + elem_2 - elem_3)
+    }
+
+    /// Like [`Self::configure`], with the recurrence gate replaced by `gate`, which is given
+    /// the `elem_1`, `elem_2`, `elem_3` columns and the selector to build its constraint
+    /// from. The gate is named `custom recurrence`.
+    ///
+    /// `init` and `assign` still compute `elem_3 = elem_1 + elem_2`, so a circuit using a
+    /// different recurrence has to lay out its own rows.
+    pub fn configure_custom<F: Field>(
+        cs: &mut ConstraintSystem<F>,
+        gate: impl Fn(
+            &mut VirtualCells<F>,
+            Column<Advice>,
+            Column<Advice>,
+            Column<Advice>,
+            Selector,
+        ) -> Expression<F>,
+    ) -> Self {
+        Self::configure_with(cs, "custom recurrence", Equality::ALL, gate)
+    }
+
+    fn configure_with<F: Field>(
+        cs: &mut ConstraintSystem<F>,
+        gate_name: &'static str,
+        equality: Equality,
+        gate: impl Fn(
+            &mut VirtualCells<F>,
+            Column<Advice>,
+            Column<Advice>,
+            Column<Advice>,
+            Selector,
+        ) -> Expression<F>,
+    ) -> Self {
         // 可変のConstraintSystem参照を引数として受け取る
         // advice columnを作成し、それぞれに等価性の制約を有効にする
         let elem_1 = cs.advice_column();
-        cs.enable_equality(elem_1);
         let elem_2 = cs.advice_column();
-        cs.enable_equality(elem_2);
         let elem_3 = cs.advice_column();
-        cs.enable_equality(elem_3);
+        for (column, enabled) in [
+            (elem_1, equality.elem_1),
+            (elem_2, equality.elem_2),
+            (elem_3, equality.elem_3),
+        ] {
+            if enabled {
+                cs.enable_equality(column);
+            }
+        }
 
         // instance columnを作成し、等価性の制約を有効にする
         let instance = cs.instance_column();
-        cs.enable_equality(instance);
+        if equality.instance {
+            cs.enable_equality(instance);
+        }
 
         // 計算の適用を制御するselectorを作成
         let q_fib = cs.selector();
 
         // フィボナッチ数列の計算を表すゲート（制約）を作成
-        cs.create_gate("fibonacci", |virtual_cells| {
-            // セレクタと各advice columnの現在の値を問い合わせる
-            let q_fib = virtual_cells.query_selector(q_fib);
-            let elem_1 = virtual_cells.query_advice(elem_1, Rotation::cur());
-            let elem_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
-            let elem_3 = virtual_cells.query_advice(elem_3, Rotation::cur());
-
-            // フィボナッチ数列の特定の性質を検証する制約を定義します。
-            // elem_1 + elem_2 - elem_3 が0となるようにする　-> elem_3 = elem_1 + elem_2 を保証する
-            vec![q_fib * (elem_1 + elem_2 - elem_3)]
+        cs.create_gate(gate_name, |virtual_cells| {
+            vec![gate(virtual_cells, elem_1, elem_2, elem_3, q_fib)]
         });
 
         // Config構造体のインスタンスを返す
@@ -71,9 +182,10 @@ impl Config {
         }
     }
 
-    fn init<F: Field>(
+    #[allow(clippy::type_complexity)]
+    pub fn init<F: Field>(
         &self,
-        mut layouter: impl Layouter<F>,
+        layouter: impl Layouter<F>,
         elem_1: Value<F>,
         elem_2: Value<F>,
     ) -> Result<
@@ -83,34 +195,20 @@ impl Config {
         ),
         Error,
     > {
-        println!("elem_1: {:?}", elem_1);
-        println!("elem_2: {:?}", elem_2);
-        layouter.assign_region(
-            || "init Fibonacci",
-            |mut region| {
-                let offset = 0;
-
-                // Enable q_fib
-                self.q_fib.enable(&mut region, offset)?;
-
-                // Assign elem_1
-                region.assign_advice(|| "elem_1", self.elem_1, offset, || elem_1)?;
-
-                // Assign elem_2
-                let elem_2 = region.assign_advice(|| "elem_2", self.elem_2, offset, || elem_2)?;
-                // let elem_3 = elem_1;
-                let elem_3 = elem_1 + elem_2.value_field().evaluate();
-                // Assign elem_3
-                let elem_3 = region.assign_advice(|| "elem_3", self.elem_3, offset, || elem_3)?;
-
-                Ok((elem_2, elem_3))
-            },
-        )
+        let (_, elem_2, elem_3) = self.assign_row(
+            layouter,
+            "init Fibonacci",
+            CellSource::Witness(elem_1),
+            CellSource::Witness(elem_2),
+            &|_| None,
+        )?;
+        Ok((elem_2, elem_3))
     }
 
-    fn assign<F: Field>(
+    #[allow(clippy::type_complexity)]
+    pub fn assign<F: Field>(
         &self,
-        mut layouter: impl Layouter<F>,
+        layouter: impl Layouter<F>,
         elem_2: &AssignedCell<F, F>,
         elem_3: &AssignedCell<F, F>,
     ) -> Result<
@@ -120,41 +218,154 @@ impl Config {
         ),
         Error,
     > {
+        let (_, elem_2, elem_3) = self.assign_row(
+            layouter,
+            "next row",
+            // elem_1 is the previous elem_2
+            CellSource::Copy(elem_2, "copy elem_2 into current elem_1"),
+            // elem_2 is the previous elem_3
+            CellSource::Copy(elem_3, "copy elem_3 into current elem_2"),
+            &|_| None,
+        )?;
+        Ok((elem_2, elem_3))
+    }
+
+    /// Lays out one `elem_1 | elem_2 | elem_3` row with `q_fib` enabled, shared by `init`
+    /// and `assign` so the two can't drift apart. `fault` is given the computed `elem_3`
+    /// and may return a replacement to assign instead, for negative tests.
+    #[allow(clippy::type_complexity)]
+    fn assign_row<F: Field>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        name: &'static str,
+        elem_1: CellSource<'_, F>,
+        elem_2: CellSource<'_, F>,
+        fault: &dyn Fn(Value<F>) -> Option<Value<F>>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>, AssignedCell<F, F>), Error> {
         layouter.assign_region(
-            || "next row",
+            || name,
             |mut region| {
                 let offset = 0;
 
                 // Enable q_fib
                 self.q_fib.enable(&mut region, offset)?;
 
-                // Copy elem_1 (which is the previous elem_2)
-                let elem_1 = elem_2.copy_advice(
-                    || "copy elem_2 into current elem_1",
-                    &mut region,
-                    self.elem_1,
-                    offset,
-                )?;
+                let elem_1 = elem_1.place(&mut region, self.elem_1, "elem_1", offset)?;
+                let elem_2 = elem_2.place(&mut region, self.elem_2, "elem_2", offset)?;
 
-                // Copy elem_2 (which is the previous elem_3)
-                let elem_2 = elem_3.copy_advice(
-                    || "copy elem_3 into current elem_2",
-                    &mut region,
-                    self.elem_2,
-                    offset,
-                )?;
                 let elem_3 = elem_1.value_field().evaluate() + elem_2.value_field().evaluate();
-                //comment next line makes constaint not satified
-                // let elem_3 = elem_1.value_field().evaluate() + elem_2.value_field().evaluate() + elem_2.value_field().evaluate();
+                let elem_3 = fault(elem_3).unwrap_or(elem_3);
                 // Assign elem_3
                 let elem_3 = region.assign_advice(|| "elem_3", self.elem_3, offset, || elem_3)?;
+                trace_cell("assign_advice", "elem_3", offset, &elem_3);
 
-                Ok((elem_2, elem_3))
+                Ok((elem_1, elem_2, elem_3))
             },
         )
     }
 
-    fn expose_public<F: Field>(
+    /// Lays out the sequence from the seeds and returns the cells of `F(1)..=F(num_terms)`,
+    /// so `terms[i]` holds `F(i + 1)`.
+    pub(crate) fn assign_terms<F: Field>(
+        &self,
+        layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        self.assign_terms_with(layouter, elem_1, elem_2, num_terms, true, &|_, _| None)
+    }
+
+    /// Like [`Self::assign_terms`], with `faults` choosing the `elem_3` of any row.
+    #[cfg(test)]
+    pub(crate) fn assign_terms_with_faults<F: Field>(
+        &self,
+        layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+        faults: &impl FaultInjector<F>,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let checked = !faults.skip_reference_check();
+        self.assign_terms_with(
+            layouter,
+            elem_1,
+            elem_2,
+            num_terms,
+            checked,
+            &|row, elem_3| faults.inject(row, elem_3),
+        )
+    }
+
+    /// Lays out the sequence, with `fault` given each row and its computed `elem_3`. When
+    /// `checked`, debug builds assert every assigned `elem_3`, faults included, against the
+    /// reference sequence for the seeds.
+    fn assign_terms_with<F: Field>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        num_terms: usize,
+        checked: bool,
+        fault: &dyn Fn(usize, Value<F>) -> Option<Value<F>>,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        // F(3) onwards, computed from the seeds apart from the laid-out cells
+        let mut reference = known(elem_1)
+            .zip(known(elem_2))
+            .filter(|_| checked && cfg!(debug_assertions))
+            .map(|(a, b)| FibIter::from_seeds(a, b).skip(2));
+
+        let (elem_1, elem_2, elem_3) = self.assign_row(
+            layouter.namespace(|| "init"),
+            "init Fibonacci",
+            CellSource::Witness(elem_1),
+            CellSource::Witness(elem_2),
+            &|elem_3| fault(0, elem_3),
+        )?;
+        check_reference(0, &elem_3, reference.as_mut().and_then(Iterator::next));
+        let mut terms = vec![elem_1, elem_2, elem_3];
+        for row in 1..num_terms.saturating_sub(2) {
+            let (_, _, next) = self.assign_row(
+                layouter.namespace(|| "next row"),
+                "next row",
+                CellSource::Copy(&terms[row], "copy elem_2 into current elem_1"),
+                CellSource::Copy(&terms[row + 1], "copy elem_3 into current elem_2"),
+                &|elem_3| fault(row, elem_3),
+            )?;
+            check_reference(row, &next, reference.as_mut().and_then(Iterator::next));
+            terms.push(next);
+        }
+        Ok(terms)
+    }
+
+    /// Like [`Self::assign_terms`], for witnesses supplied as one `Value` holding the whole
+    /// sequence `F(1)..=F(num_terms)`. Only the seeds are taken from it; the later terms are
+    /// implied by the gate and checked against the vector in debug builds. An unknown vector
+    /// assigns unknown cells, as keygen needs.
+    pub fn assign_from_value_vec<F: Field>(
+        &self,
+        layouter: impl Layouter<F>,
+        values: Value<Vec<F>>,
+        num_terms: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let elem_1 = values.as_ref().map(|values| values[0]);
+        let elem_2 = values.as_ref().map(|values| values[1]);
+        let terms = self.assign_terms(layouter, elem_1, elem_2, num_terms)?;
+        #[cfg(debug_assertions)]
+        if let Some(values) = known(values.as_ref()) {
+            let assigned: Vec<F> = terms
+                .iter()
+                .filter_map(|cell| known(cell.value().copied()))
+                .collect();
+            assert_eq!(
+                &assigned, values,
+                "the witness vector is not a Fibonacci sequence"
+            );
+        }
+        Ok(terms)
+    }
+
+    pub fn expose_public<F: Field>(
         &self,
         mut layouter: impl Layouter<F>,
         cell: &AssignedCell<F, F>,
@@ -162,95 +373,1546 @@ impl Config {
     ) -> Result<(), Error> {
         layouter.constrain_instance(cell.cell(), self.instance, row)
     }
+
+    /// Lays out the first row and returns a [`FibonacciBuilder`] to extend the sequence
+    /// from, a few rows at a time.
+    pub fn builder<F: Field>(
+        &self,
+        layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+    ) -> Result<FibonacciBuilder<F>, Error> {
+        let (elem_2, elem_3) = self.init(layouter, elem_1, elem_2)?;
+        Ok(FibonacciBuilder {
+            config: *self,
+            elem_2,
+            elem_3,
+        })
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use halo2_proofs::{circuit::SimpleFloorPlanner, dev::MockProver, pasta::Fp};
+/// The running `(elem_2, elem_3)` cells of a sequence being laid out, so it can be extended
+/// across several calls within one synthesis without redoing the earlier rows.
+#[derive(Clone, Debug)]
+pub struct FibonacciBuilder<F: Field> {
+    config: Config,
+    elem_2: AssignedCell<F, F>,
+    elem_3: AssignedCell<F, F>,
+}
 
-    use super::*;
+impl<F: Field> FibonacciBuilder<F> {
+    /// Lays out `steps` more rows, one term each.
+    pub fn extend(&mut self, mut layouter: impl Layouter<F>, steps: usize) -> Result<(), Error> {
+        for _ in 0..steps {
+            (self.elem_2, self.elem_3) = self.config.assign(
+                layouter.namespace(|| "next row"),
+                &self.elem_2,
+                &self.elem_3,
+            )?;
+        }
+        Ok(())
+    }
 
-    /*
-        1, 1, 2, 3, 5, 8, 13, ...
+    /// The latest term.
+    pub fn output(&self) -> &AssignedCell<F, F> {
+        &self.elem_3
+    }
+}
 
-        | elem_1 | elem_2 | sum | q_fib | instance
-        --------------------------------
-        |    1   |    1   |  2  |   1   | 55
-        |    1   |    2   |  3  |   1
-        |    2   |    3   |  5  |   1
-        |        |        |     |   0
+/// The Fibonacci circuit: starts from the seeds `elem_1`, `elem_2` and exposes the
+/// `num_terms`-th term of the sequence as the single public input.
+///
+/// The output is exposed in the clear. Exposing only a hash of it would need an in-circuit
+/// hash chip (e.g. the Poseidon chip in `halo2_gadgets`), which this crate doesn't depend on.
+/// The same goes for proving the seeds open a public (e.g. Pedersen) commitment, which
+/// needs an in-circuit ECC chip.
+///
+/// Revealing a term at an index the verifier picks after the sequence is committed is not
+/// supported: it needs a later-phase challenge, and `halo2_proofs` 0.2 has no multi-phase
+/// advice or challenge API. Fixing the index before proving (e.g. by the term count) is a
+/// different statement, since the prover then knows which term will be opened.
+#[derive(Clone, Debug)]
+pub struct FibonacciCircuit<F: Field> {
+    elem_1: Value<F>, // 1
+    elem_2: Value<F>, // 1
+    num_terms: usize,
+    allow_trivial: bool,
+    expose_boundary: bool,
+    expose_seeds: bool,
+    tolerant_instance: bool,
+    no_public_output: bool,
+}
+
+impl<F: FieldExt> FibonacciCircuit<F> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Self {
+        Self::from_seeds(F::from(a), F::from(b), num_terms)
+    }
+
+    /// Starts the sequence from arbitrary field elements.
+    pub fn from_seeds(a: F, b: F, num_terms: usize) -> Self {
+        Self {
+            elem_1: Value::known(a),
+            elem_2: Value::known(b),
+            num_terms,
+            allow_trivial: false,
+            expose_boundary: false,
+            expose_seeds: false,
+            tolerant_instance: false,
+            no_public_output: false,
+        }
+    }
 
+    /// Starts the sequence from uniformly random seeds, for benchmarks and fuzzing that
+    /// shouldn't only exercise `1, 1`. Returns the circuit with its public inputs.
+    pub fn random(mut rng: impl RngCore, num_terms: usize) -> (Self, Vec<F>) {
+        let circuit = Self::from_seeds(F::random(&mut rng), F::random(&mut rng), num_terms);
+        let public_inputs = circuit.public_inputs().expect("the seeds are known");
+        (circuit, public_inputs)
+    }
 
+    /// Truncates the sequence to the terms computed before it first wraps around the field
+    /// modulus (see [`wraps_before`]), so the output keeps its integer meaning.
+    /// [`Self::num_terms`] reports how many terms are left.
+    pub fn halt_on_wraparound(mut self) -> Self {
+        if let Some((a, b)) = known(self.elem_1.zip(self.elem_2)) {
+            self.num_terms = wraps_before(a, b, self.num_terms);
+        }
+        self
+    }
 
-    */
+    /// Replaces the number of terms, keeping the seeds and flags.
+    pub fn with_num_terms(mut self, num_terms: usize) -> Self {
+        self.num_terms = num_terms;
+        self
+    }
 
-    #[derive(Default)]
+    /// Replaces the seeds, keeping the number of terms and flags.
+    pub fn with_seeds(mut self, a: u64, b: u64) -> Self {
+        self.elem_1 = Value::known(F::from(a));
+        self.elem_2 = Value::known(F::from(b));
+        self
+    }
 
-    struct MyCircuit<F: Field> {
-        elem_1: Value<F>, // 1
-        elem_2: Value<F>, // 1
+    /// Exposes `[elem_1, elem_2, F(n - 1), F(n)]` instead of just the output, so proofs of
+    /// consecutive segments can be checked to line up.
+    pub fn expose_boundary(mut self, expose: bool) -> Self {
+        self.expose_boundary = expose;
+        self
     }
 
-    impl<F: Field> Circuit<F> for MyCircuit<F> {
-        type Config = Config;
+    /// Exposes `[elem_1, elem_2, F(n)]` instead of just the output, so the verifier sees
+    /// which sequence the output belongs to. [`Self::expose_boundary`] takes precedence.
+    pub fn expose_seeds(mut self, expose: bool) -> Self {
+        self.expose_seeds = expose;
+        self
+    }
 
-        type FloorPlanner = SimpleFloorPlanner;
+    /// Allows the degenerate all-zero seeds, which are rejected by [`Self::validate`]
+    /// by default.
+    pub fn allow_trivial(mut self, allow: bool) -> Self {
+        self.allow_trivial = allow;
+        self
+    }
 
-        fn without_witnesses(&self) -> Self {
-            Self::default()
+    /// Exposes nothing, so the proof only shows that some sequence of `num_terms` terms
+    /// was computed, and is verified against an empty instance vector. Takes precedence
+    /// over the other exposure flags.
+    pub fn no_public_output(mut self, hide: bool) -> Self {
+        self.no_public_output = hide;
+        self
+    }
+
+    /// Lets [`Self::check_public_inputs`] accept an instance vector padded with trailing
+    /// zeros, which halo2 pads with anyway. Nonzero padding is still rejected: nothing
+    /// constrains it, but it changes the instance commitment, so the proof would not verify.
+    pub fn tolerant_instance(mut self, tolerant: bool) -> Self {
+        self.tolerant_instance = tolerant;
+        self
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.num_terms
+    }
+
+    pub(crate) fn seeds(&self) -> (Value<F>, Value<F>) {
+        (self.elem_1, self.elem_2)
+    }
+
+    /// Constrains the output to the circuit constant `expected` instead of exposing it, so
+    /// the verifying key alone fixes the statement.
+    pub fn with_constant_output(self, expected: F) -> ConstantOutputCircuit<F> {
+        ConstantOutputCircuit::new(self, expected)
+    }
+
+    /// Pads the layout to `rows` rows so every term count that fits shares one verifying
+    /// key; see [`PaddedCircuit`].
+    pub fn pad_to_rows(self, rows: usize) -> Result<PaddedCircuit<F>, FibError> {
+        PaddedCircuit::new(self, rows)
+    }
+
+    /// Requires the seeds to satisfy the public relation `x * a + y * b = c`, enforced by an
+    /// extra gate; see [`SeedRelationCircuit`].
+    pub fn with_seed_relation(self, x: F, y: F, c: F) -> SeedRelationCircuit<F> {
+        SeedRelationCircuit::new(self, x, y, c)
+    }
+
+    /// Calls `observer` with each row and the `elem_3` computed there as the circuit is
+    /// synthesized; see [`ObservedCircuit`].
+    pub fn with_observer(self, observer: Box<dyn Fn(usize, F)>) -> ObservedCircuit<F> {
+        ObservedCircuit::new(self, observer)
+    }
+
+    /// A circuit whose seeds are computed by `seed_fn` only when synthesis needs them, e.g.
+    /// fetched from an external source; see [`LazyCircuit`].
+    pub fn lazy(seed_fn: impl Fn() -> (F, F) + 'static, num_terms: usize) -> LazyCircuit<F> {
+        LazyCircuit::new(seed_fn, num_terms)
+    }
+
+    /// Exposes `scale * F(n) + offset` instead of `F(n)`, enforced by an extra gate. Only
+    /// affine maps are supported, since an arbitrary closure can't be constrained.
+    pub fn with_output_transform(self, scale: F, offset: F) -> TransformedCircuit<F> {
+        TransformedCircuit::new(self, scale, offset)
+    }
+
+    /// Rows used by the layout: one per term after the two seeds.
+    pub fn rows(&self) -> usize {
+        self.num_terms.saturating_sub(2)
+    }
+
+    /// The `num_terms`-th term, or `None` for a circuit without witnesses.
+    pub fn output(&self) -> Option<F> {
+        let (mut a, mut b) = (known(self.elem_1)?, known(self.elem_2)?);
+        for _ in 2..self.num_terms {
+            (a, b) = (b, a + b);
         }
+        Some(b)
+    }
 
-        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-            Self::Config::configure(meta)
+    /// The `(elem_1, elem_2, elem_3)` values of the final row, i.e.
+    /// `(F(n - 2), F(n - 1), F(n))`, without laying out the rest of the table.
+    pub fn last_row(&self) -> Option<(F, F, F)> {
+        let (mut a, mut b) = (known(self.elem_1)?, known(self.elem_2)?);
+        for _ in 3..self.num_terms {
+            (a, b) = (b, a + b);
         }
+        Some((a, b, a + b))
+    }
 
-        fn synthesize(
-            &self,
-            config: Self::Config,
-            mut layouter: impl Layouter<F>,
-        ) -> Result<(), Error> {
-            // elem_2 = 1, elem_3 = 2
-            let (mut elem_2, mut elem_3) =
-                config.init(layouter.namespace(|| "init"), self.elem_1, self.elem_2)?;
+    /// The assignment as an ASCII table laid out like the one at the top of this module,
+    /// with one row per layout row and the trailing row where `q_fib` is off. Values below
+    /// 2^128 are printed in decimal, larger ones in hex; without witnesses the value cells
+    /// are left blank.
+    pub fn trace_table(&self) -> String {
+        let values: Vec<[String; 3]> = match known(self.elem_1.zip(self.elem_2)) {
+            Some((a, b)) => compute_witness(a, b, self.num_terms)
+                .into_iter()
+                .map(|row| row.map(trace_value))
+                .collect(),
+            None => vec![Default::default(); self.num_terms.saturating_sub(2).max(1)],
+        };
+        let rows: Vec<[String; 4]> = values
+            .into_iter()
+            .map(|[x, y, z]| [x, y, z, "1".to_string()])
+            .chain([["", "", "", "0"].map(String::from)])
+            .collect();
+
+        let header = ["elem_1", "elem_2", "sum", "q_fib"].map(String::from);
+        let widths: Vec<usize> = (0..4)
+            .map(|i| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[i].len())
+                    .max()
+                    .unwrap_or(0)
+                    + 2
+            })
+            .collect();
+        let line = |cells: &[String; 4]| {
+            let line: String = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| {
+                    // centred, with the odd space on the left as in the table above
+                    let left = (width - cell.len()).div_ceil(2);
+                    let right = width - cell.len() - left;
+                    format!("|{}{}{}", " ".repeat(left), cell, " ".repeat(right))
+                })
+                .collect();
+            line.trim_end().to_string()
+        };
+
+        let header = line(&header);
+        let rule = "-".repeat(header.len() + 1);
+        let mut table = vec![header, rule];
+        table.extend(rows.iter().map(line));
+        table.join("\n") + "\n"
+    }
 
-            // 1 + 2 = 3
-            for _i in 3..10 {
-                let (_, new_elem_3) =
-                    config.assign(layouter.namespace(|| "next row"), &elem_2, &elem_3)?;
+    /// Checks that the circuit describes a meaningful statement that fits in a circuit of
+    /// size at most `2^MAX_SUPPORTED_K`.
+    pub fn validate(&self) -> Result<(), FibError> {
+        // the layout always computes F(3)
+        if self.num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                self.num_terms
+            )));
+        }
+        if self.num_terms > max_terms_for_k(MAX_SUPPORTED_K) {
+            return Err(FibError::TooManyTerms(self.num_terms));
+        }
+        let trivial = known(self.elem_1.zip(self.elem_2))
+            .map(|(a, b)| bool::from(a.is_zero()) && bool::from(b.is_zero()))
+            .unwrap_or(false);
+        if trivial && !self.allow_trivial {
+            return Err(FibError::BadPublicInputs(
+                "both seeds are zero, so every term is zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// What each instance row means under the circuit's exposure flags.
+    pub fn instance_spec(&self) -> Vec<InstanceField> {
+        if self.no_public_output {
+            vec![]
+        } else if self.expose_boundary {
+            vec![
+                InstanceField::SeedA,
+                InstanceField::SeedB,
+                InstanceField::Term(self.num_terms - 1),
+                InstanceField::Output,
+            ]
+        } else if self.expose_seeds {
+            vec![
+                InstanceField::SeedA,
+                InstanceField::SeedB,
+                InstanceField::Output,
+            ]
+        } else {
+            vec![InstanceField::Output]
+        }
+    }
+
+    /// Number of public inputs the circuit exposes.
+    pub fn num_public_inputs(&self) -> usize {
+        self.instance_spec().len()
+    }
+
+    /// The public inputs the circuit expects, or `None` for a circuit without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let (a, b) = (known(self.elem_1)?, known(self.elem_2)?);
+        self.instance_spec()
+            .into_iter()
+            .map(|field| match field {
+                InstanceField::SeedA => Some(a),
+                InstanceField::SeedB => Some(b),
+                InstanceField::Term(i) => Self::from_seeds(a, b, i).output(),
+                InstanceField::Output => self.output(),
+            })
+            .collect()
+    }
 
-                elem_2 = elem_3;
-                elem_3 = new_elem_3;
+    /// Checks the shape of a public-input vector before it reaches halo2, which would
+    /// otherwise reject it with an opaque error or silently zero-pad it.
+    ///
+    /// The length has to match exactly, unless [`Self::tolerant_instance`] is set.
+    pub fn check_public_inputs(&self, public_inputs: &[F]) -> Result<(), FibError> {
+        let expected = self.num_public_inputs();
+        if self.tolerant_instance && public_inputs.len() > expected {
+            if let Some(row) = public_inputs[expected..]
+                .iter()
+                .position(|value| !bool::from(value.is_zero()))
+            {
+                return Err(FibError::BadPublicInputs(format!(
+                    "instance row {} is padding but not zero",
+                    expected + row
+                )));
             }
-            config.expose_public(layouter, &elem_3, 0)?;
-            Ok(())
+            return Ok(());
         }
+        if public_inputs.len() != expected {
+            return Err(FibError::BadPublicInputs(format!(
+                "expected {} public input(s), got {}",
+                self.num_public_inputs(),
+                public_inputs.len()
+            )));
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_fib() {
-        let circuit = MyCircuit {
-            elem_1: Value::known(Fp::one()),
-            elem_2: Value::known(Fp::one()),
+    /// Validates the statement and runs it through `MockProver`.
+    pub fn verify_mock(&self, k: u32, public_inputs: &[F]) -> Result<(), FibError> {
+        self.validate()?;
+        self.check_public_inputs(public_inputs)?;
+        let prover = MockProver::run(k, self, vec![public_inputs.to_vec()])?;
+        prover.verify().map_err(FibError::Unsatisfied)
+    }
+
+    /// Like [`Self::verify_mock`], but checks the public inputs against the number of
+    /// `constrain_instance` calls the layout actually makes rather than against
+    /// [`Self::num_public_inputs`], so an instance value nothing references is an error.
+    pub fn verify_mock_strict(&self, k: u32, public_inputs: &[F]) -> Result<(), FibError> {
+        let referenced = CircuitStats::measure(self)?.instance_copies;
+        if public_inputs.len() != referenced {
+            return Err(FibError::BadPublicInputs(format!(
+                "the circuit references {} instance cell(s), got {} public input(s)",
+                referenced,
+                public_inputs.len()
+            )));
+        }
+        self.verify_mock(k, public_inputs)
+    }
+}
+
+/// A [`FibonacciCircuit`] whose term count `N` is fixed at compile time, so the sequence
+/// comes out as an `[F; N]`.
+#[derive(Clone, Debug)]
+pub struct FixedFibonacciCircuit<F: Field, const N: usize>(FibonacciCircuit<F>);
+
+impl<F: FieldExt, const N: usize> FixedFibonacciCircuit<F, N> {
+    pub fn new(a: u64, b: u64) -> Self {
+        const { assert!(N >= 3, "the layout needs at least 3 terms") };
+        Self(FibonacciCircuit::new(a, b, N))
+    }
+
+    /// The terms `F(1)..=F(N)`, or `None` for a circuit without witnesses.
+    pub fn terms(&self) -> Option<[F; N]> {
+        let mut terms = [F::zero(); N];
+        terms[0] = known(self.0.elem_1)?;
+        terms[1] = known(self.0.elem_2)?;
+        for i in 2..N {
+            terms[i] = terms[i - 2] + terms[i - 1];
+        }
+        Some(terms)
+    }
+
+    /// The underlying circuit, for [`prove`](crate::proof::prove) and friends.
+    pub fn circuit(&self) -> &FibonacciCircuit<F> {
+        &self.0
+    }
+}
+
+impl<F: Field, const N: usize> Circuit<F> for FixedFibonacciCircuit<F, N> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FibonacciCircuit::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        self.0.synthesize(config, layouter)
+    }
+}
+
+impl FibonacciCircuit<Fp> {
+    /// Like [`Self::new`], for seeds that don't fit in a `u64`.
+    pub fn new_u128(a: u128, b: u128, num_terms: usize) -> Self {
+        Self::from_seeds(fp_from_u128(a), fp_from_u128(b), num_terms)
+    }
+
+    /// Starts the sequence from seeds given as 32-byte little-endian encodings, e.g. as
+    /// received over the wire. Encodings of values at or above the modulus are rejected
+    /// rather than reduced.
+    pub fn from_le_bytes(a: &[u8; 32], b: &[u8; 32], num_terms: usize) -> Result<Self, FibError> {
+        let decode = |bytes: &[u8; 32], name: &str| {
+            Option::from(Fp::from_repr(*bytes)).ok_or_else(|| {
+                FibError::BadPublicInputs(format!("seed {} is not a canonical field element", name))
+            })
         };
-        let instance = Fp::from(55);
-        let mut public_input = vec![instance];
-        let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+        Ok(Self::from_seeds(
+            decode(a, "a")?,
+            decode(b, "b")?,
+            num_terms,
+        ))
+    }
+}
 
-        prover.assert_satisfied();
+/// `x` as a field element, assembled from its two 64-bit halves.
+pub fn fp_from_u128(x: u128) -> Fp {
+    let shift = Fp::from(1 << 32).square();
+    Fp::from((x >> 64) as u64) * shift + Fp::from(x as u64)
+}
+
+/// The `[elem_1, elem_2, elem_3]` values of every row the layout assigns for the seeds
+/// `a, b`, computed without a layouter.
+pub fn compute_witness<F: Field>(a: F, b: F, num_terms: usize) -> Vec<[F; 3]> {
+    let rows = num_terms.saturating_sub(2).max(1);
+    let mut table = Vec::with_capacity(rows);
+    let (mut elem_1, mut elem_2) = (a, b);
+    for _ in 0..rows {
+        let elem_3 = elem_1 + elem_2;
+        table.push([elem_1, elem_2, elem_3]);
+        (elem_1, elem_2) = (elem_2, elem_3);
+    }
+    table
+}
+
+/// A cell of [`FibonacciCircuit::trace_table`].
+fn trace_value<F: FieldExt>(value: F) -> String {
+    let lower = value.get_lower_128();
+    if F::from_u128(lower) == value {
+        lower.to_string()
+    } else {
+        format!("{:?}", value)
     }
+}
 
-    #[cfg(feature = "dev-graph")]
-    #[test]
-    fn print_fibo() {
-        use plotters::prelude::*;
-        let root = BitMapBackend::new("fib-layout.png", (1024, 3096)).into_drawing_area();
-        root.fill(&WHITE).unwrap();
-        let root = root.titled("Fib Layout", ("sans-serif", 60)).unwrap();
+/// The meaning of one instance row; see [`FibonacciCircuit::instance_spec`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstanceField {
+    /// The first seed, `elem_1`.
+    SeedA,
+    /// The second seed, `elem_2`.
+    SeedB,
+    /// The term at the given 1-based index.
+    Term(usize),
+    /// The final term `F(num_terms)`.
+    Output,
+}
+
+/// The largest circuit size supported. The two-adicity of the Pasta fields would allow
+/// 32, but `Params::new` only accepts `k < 32`.
+pub const MAX_SUPPORTED_K: u32 = 31;
+
+/// The most terms a circuit of size `2^k` fits: one per usable row, plus the two seeds.
+pub fn max_terms_for_k(k: u32) -> usize {
+    (1usize << k).saturating_sub(6) + 2
+}
+
+/// The smallest `k` whose usable rows fit a `num_terms` circuit.
+pub fn min_k(num_terms: usize) -> u32 {
+    crate::proof::k_for_rows(num_terms.saturating_sub(2))
+}
 
-        let circuit = MyCircuit {
-            elem_1: Value::known(Fp::one()),
-            elem_2: Value::known(Fp::one()),
+/// Runs `circuit` through `MockProver` against a single instance column, returning whether
+/// every constraint holds instead of panicking like `assert_satisfied`. Errors are kept for
+/// circuits that can't be laid out at all, e.g. when `k` is too small.
+pub fn is_satisfied<F: FieldExt, C: Circuit<F>>(
+    circuit: &C,
+    public_inputs: &[F],
+    k: u32,
+) -> Result<bool, FibError> {
+    let prover = MockProver::run(k, circuit, vec![public_inputs.to_vec()])?;
+    Ok(prover.verify().is_ok())
+}
+
+/// How many of the first `num_terms` terms starting from `a, b` are computed before the
+/// sequence first wraps around the field modulus, i.e. while every term still equals its
+/// integer counterpart.
+pub fn wraps_before<F: FieldExt>(a: F, b: F, num_terms: usize) -> usize {
+    let (mut a, mut b) = (a, b);
+    for i in 2..num_terms {
+        let next = a + b;
+        // without wraparound the sum is at least as large as both summands
+        if next < a || next < b {
+            return i;
+        }
+        (a, b) = (b, next);
+    }
+    num_terms
+}
+
+/// Overrides the `elem_3` the layout assigns on chosen rows, so tests can check that a
+/// wrong witness is caught. Row `i` of [`Config::assign_terms_with_faults`] computes
+/// `F(i + 3)`.
+#[cfg(test)]
+pub(crate) trait FaultInjector<F: Field> {
+    /// The value to assign on `row` instead of the computed `elem_3`, if any.
+    fn inject(&self, row: usize, elem_3: Value<F>) -> Option<Value<F>>;
+
+    /// Whether the faults skip the debug reference check and reach the constraints.
+    fn skip_reference_check(&self) -> bool {
+        false
+    }
+}
+
+/// Where a row takes its `elem_1` / `elem_2` from.
+enum CellSource<'a, F: Field> {
+    /// A fresh witness (the seeds).
+    Witness(Value<F>),
+    /// A copy of an earlier cell, with the annotation of the copy.
+    Copy(&'a AssignedCell<F, F>, &'static str),
+}
+
+impl<F: Field> CellSource<'_, F> {
+    fn place(
+        &self,
+        region: &mut Region<'_, F>,
+        column: Column<Advice>,
+        name: &'static str,
+        offset: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let (cell, kind) = match self {
+            CellSource::Witness(value) => (
+                region.assign_advice(|| name, column, offset, || *value)?,
+                "assign_advice",
+            ),
+            CellSource::Copy(cell, annotation) => (
+                cell.copy_advice(|| *annotation, region, column, offset)?,
+                "copy_advice",
+            ),
         };
+        trace_cell(kind, name, offset, &cell);
+        Ok(cell)
+    }
+}
+
+/// Emits a `tracing` event for an assigned cell when the `trace` feature is on.
+#[cfg(feature = "trace")]
+fn trace_cell<F: Field>(kind: &str, column: &str, offset: usize, cell: &AssignedCell<F, F>) {
+    tracing::trace!(kind, column, offset, value = ?cell.value(), "cell assigned");
+}
+
+#[cfg(not(feature = "trace"))]
+fn trace_cell<F: Field>(_: &str, _: &str, _: usize, _: &AssignedCell<F, F>) {}
+
+/// Asserts that the `elem_3` assigned on `row` is the `expected` reference term, so witness
+/// bugs surface before the prover reports an opaque failure. A `None` skips the check.
+fn check_reference<F: Field>(row: usize, elem_3: &AssignedCell<F, F>, expected: Option<F>) {
+    if let (Some(expected), Some(actual)) = (expected, known(elem_3.value().copied())) {
+        assert_eq!(
+            actual, expected,
+            "elem_3 of row {} diverged from the reference sequence",
+            row
+        );
+    }
+}
+
+/// Extracts the value of a known `Value`.
+pub(crate) fn known<V: Copy>(value: Value<V>) -> Option<V> {
+    let mut out = None;
+    value.map(|v| out = Some(v));
+    out
+}
+
+/// The instance vector `circuit` expects under its exposure flags, computed independently of
+/// [`FibonacciCircuit::public_inputs`] so tests don't lay it out by hand.
+#[cfg(test)]
+pub(crate) fn expected_instance(
+    circuit: &FibonacciCircuit<halo2_proofs::pasta::Fp>,
+) -> Vec<halo2_proofs::pasta::Fp> {
+    let (a, b) = known(circuit.elem_1.zip(circuit.elem_2)).expect("circuit has witnesses");
+    let mut terms = vec![a, b];
+    while terms.len() < circuit.num_terms.max(3) {
+        terms.push(terms[terms.len() - 2] + terms[terms.len() - 1]);
+    }
+    let n = terms.len();
+    if circuit.no_public_output {
+        vec![]
+    } else if circuit.expose_boundary {
+        vec![a, b, terms[n - 2], terms[n - 1]]
+    } else if circuit.expose_seeds {
+        vec![a, b, terms[n - 1]]
+    } else {
+        vec![terms[n - 1]]
+    }
+}
+
+impl<F: Field> Circuit<F> for FibonacciCircuit<F> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::Config::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        self.synthesize_at(config, layouter, 0)?;
+        Ok(())
+    }
+}
+
+impl<F: Field> FibonacciCircuit<F> {
+    /// Lays out the circuit with its public inputs starting at instance row `first_row`,
+    /// so several circuits can share one instance column, and returns the cells of every
+    /// term.
+    pub(crate) fn synthesize_at(
+        &self,
+        config: Config,
+        mut layouter: impl Layouter<F>,
+        first_row: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        // 1, 1, 2, 3, 5, ...
+        let terms = config.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.elem_1,
+            self.elem_2,
+            self.num_terms,
+        )?;
+        let n = terms.len();
+
+        if self.no_public_output {
+            // a pure satisfiability proof
+        } else if self.expose_boundary {
+            let boundary = [&terms[0], &terms[1], &terms[n - 2], &terms[n - 1]];
+            for (row, cell) in boundary.into_iter().enumerate() {
+                config.expose_public(layouter.namespace(|| "boundary"), cell, first_row + row)?;
+            }
+        } else if self.expose_seeds {
+            let statement = [&terms[0], &terms[1], &terms[n - 1]];
+            for (row, cell) in statement.into_iter().enumerate() {
+                config.expose_public(
+                    layouter.namespace(|| "seeds and output"),
+                    cell,
+                    first_row + row,
+                )?;
+            }
+        } else {
+            config.expose_public(layouter, &terms[n - 1], first_row)?;
+        }
+        Ok(terms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::VerifyFailure;
+    use halo2_proofs::pasta::EqAffine;
+    use halo2_proofs::poly::commitment::Params;
+
+    use super::*;
+
+    /*
+        1, 1, 2, 3, 5, 8, 13, ...
+
+        | elem_1 | elem_2 | sum | q_fib | instance
+        --------------------------------
+        |    1   |    1   |  2  |   1   | 55
+        |    1   |    2   |  3  |   1
+        |    2   |    3   |  5  |   1
+        |        |        |     |   0
+
+
+
+    */
+
+    #[test]
+    fn test_fib() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let public_input = expected_instance(&circuit);
+        assert_eq!(public_input, vec![Fp::from(55)]);
+        let prover = MockProver::run(5, &circuit, vec![public_input]).unwrap();
+
+        prover.assert_satisfied();
+    }
+
+    /// Calls `init` and `assign` directly and checks the cells against the table above.
+    #[derive(Default)]
+    struct RowCircuit;
+
+    impl Circuit<Fp> for RowCircuit {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Self::Config::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let value = |cell: &AssignedCell<Fp, Fp>| known(cell.value().copied()).unwrap();
+            let one = Value::known(Fp::one());
+
+            let (elem_2, elem_3) = config.init(layouter.namespace(|| "init"), one, one)?;
+            assert_eq!((value(&elem_2), value(&elem_3)), (Fp::from(1), Fp::from(2)));
+
+            let (elem_2, elem_3) =
+                config.assign(layouter.namespace(|| "next row"), &elem_2, &elem_3)?;
+            assert_eq!((value(&elem_2), value(&elem_3)), (Fp::from(2), Fp::from(3)));
+
+            let (elem_2, elem_3) =
+                config.assign(layouter.namespace(|| "next row"), &elem_2, &elem_3)?;
+            assert_eq!((value(&elem_2), value(&elem_3)), (Fp::from(3), Fp::from(5)));
+
+            config.expose_public(layouter, &elem_3, 0)
+        }
+    }
+
+    #[test]
+    fn test_compute_witness() {
+        let rows = compute_witness(Fp::one(), Fp::one(), 5);
+        let expected = [[1, 1, 2], [1, 2, 3], [2, 3, 5]].map(|row| row.map(Fp::from));
+        assert_eq!(rows, expected);
+
+        let circuit = FibonacciCircuit::<Fp>::new(2, 5, 20);
+        let rows = compute_witness(Fp::from(2), Fp::from(5), 20);
+        assert_eq!(rows.len(), circuit.rows());
+        let last = rows[rows.len() - 1];
+        assert_eq!(circuit.last_row(), Some((last[0], last[1], last[2])));
+    }
+
+    #[test]
+    fn test_init_and_assign_rows() {
+        let prover = MockProver::run(5, &RowCircuit, vec![vec![Fp::from(5)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    /// Two chips side by side; the second one is fed a row that breaks its gate.
+    #[derive(Default)]
+    struct TwoChipCircuit;
+
+    impl Circuit<Fp> for TwoChipCircuit {
+        type Config = (Config, Config);
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            (
+                Config::configure_named(meta, "fib_a"),
+                Config::configure_named(meta, "fib_b"),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            (chip_a, chip_b): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let one = Value::known(Fp::one());
+            chip_a.assign_terms(layouter.namespace(|| "chip a"), one, one, 5)?;
+
+            layouter.assign_region(
+                || "bad row",
+                |mut region| {
+                    chip_b.q_fib.enable(&mut region, 0)?;
+                    region.assign_advice(|| "elem_1", chip_b.elem_1, 0, || one)?;
+                    region.assign_advice(|| "elem_2", chip_b.elem_2, 0, || one)?;
+                    region.assign_advice(|| "elem_3", chip_b.elem_3, 0, || one + one + one)
+                },
+            )?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_gate_names_distinguish_chips() {
+        let prover = MockProver::run(5, &TwoChipCircuit, vec![vec![], vec![]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 1);
+        let message = failures[0].to_string();
+        assert!(message.contains("fib_b"), "{}", message);
+        assert!(!message.contains("fib_a"), "{}", message);
+    }
+
+    /// The sequence computed with a formula that adds `elem_2` twice. Laid out by hand so the debug reference check doesn't fire first.
+    #[derive(Default)]
+    struct DoubledElem2Circuit;
+
+    impl Circuit<Fp> for DoubledElem2Circuit {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Self::Config::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "doubled elem_2",
+                |mut region| {
+                    let (mut elem_1, mut elem_2) =
+                        (Value::known(Fp::one()), Value::known(Fp::one()));
+                    for offset in 0..3 {
+                        config.q_fib.enable(&mut region, offset)?;
+                        region.assign_advice(|| "elem_1", config.elem_1, offset, || elem_1)?;
+                        region.assign_advice(|| "elem_2", config.elem_2, offset, || elem_2)?;
+                        let elem_3 = elem_1 + elem_2 + elem_2;
+                        region.assign_advice(|| "elem_3", config.elem_3, offset, || elem_3)?;
+                        (elem_1, elem_2) = (elem_2, elem_3);
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_doubled_elem_2_breaks_the_gate() {
+        let prover = MockProver::run(5, &DoubledElem2Circuit, vec![vec![]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 3);
+        for failure in &failures {
+            assert!(
+                matches!(failure, VerifyFailure::ConstraintNotSatisfied { .. }),
+                "{}",
+                failure
+            );
+            assert!(failure.to_string().contains("fibonacci"), "{}", failure);
+        }
+    }
+
+    /// Adds one to `elem_3` on a single row, past the reference check.
+    #[derive(Clone)]
+    struct OffByOne(usize);
+
+    impl FaultInjector<Fp> for OffByOne {
+        fn inject(&self, row: usize, elem_3: Value<Fp>) -> Option<Value<Fp>> {
+            (row == self.0).then(|| elem_3 + Value::known(Fp::one()))
+        }
+
+        fn skip_reference_check(&self) -> bool {
+            true
+        }
+    }
+
+    /// `OffByOne`, with the reference check left on.
+    #[derive(Clone)]
+    struct Checked(OffByOne);
+
+    impl FaultInjector<Fp> for Checked {
+        fn inject(&self, row: usize, elem_3: Value<Fp>) -> Option<Value<Fp>> {
+            self.0.inject(row, elem_3)
+        }
+    }
+
+    /// The plain 10-term sequence with the faults of `I` injected.
+    struct FaultyCircuit<I = OffByOne>(I);
+
+    impl<I: FaultInjector<Fp> + Clone> Circuit<Fp> for FaultyCircuit<I> {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.clone())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Self::Config::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let one = Value::known(Fp::one());
+            let terms = config.assign_terms_with_faults(
+                layouter.namespace(|| "sequence"),
+                one,
+                one,
+                10,
+                &self.0,
+            )?;
+            config.expose_public(layouter, &terms[9], 0)
+        }
+    }
+
+    #[test]
+    fn test_injected_fault_is_caught() {
+        for row in [0, 3, 7] {
+            let circuit = FaultyCircuit(OffByOne(row));
+            let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+            let failures = prover.verify().unwrap_err();
+            // later rows copy the faulty term and stay consistent with it, so only the
+            // faulty row breaks the gate; the output no longer matches either
+            assert!(failures
+                .iter()
+                .any(|failure| matches!(failure, VerifyFailure::ConstraintNotSatisfied { .. })));
+        }
+
+        // a fault on a row that doesn't exist leaves the circuit intact
+        let prover =
+            MockProver::run(5, &FaultyCircuit(OffByOne(8)), vec![vec![Fp::from(55)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_describe_failure() {
+        let prover =
+            MockProver::run(5, &FaultyCircuit(OffByOne(3)), vec![vec![Fp::from(55)]]).unwrap();
+        let hints: Vec<String> = prover
+            .verify()
+            .unwrap_err()
+            .iter()
+            .map(crate::error::describe_failure)
+            .collect();
+        assert!(hints.contains(
+            &"a computed elem_3 doesn't equal elem_1 + elem_2 in Region 3 ('next row') at \
+              offset 0; check your assign logic"
+                .to_string()
+        ));
+
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(56)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .map(crate::error::describe_failure)
+            .any(|hint| hint.starts_with("the public input") && hint.contains("instance vector")));
+    }
+
+    /// Lays the sequence out from a single `Value<Vec<Fp>>` witness.
+    struct ValueVecCircuit(Value<Vec<Fp>>);
+
+    impl Circuit<Fp> for ValueVecCircuit {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(Value::unknown())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Self::Config::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let terms = config.assign_from_value_vec(
+                layouter.namespace(|| "sequence"),
+                self.0.clone(),
+                10,
+            )?;
+            config.expose_public(layouter, &terms[9], 0)
+        }
+    }
+
+    /// Lays out `elem_3 = 2 * elem_1 + elem_2` from `1, 1` under a custom gate.
+    struct CustomGateCircuit(usize);
+
+    impl Circuit<Fp> for CustomGateCircuit {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Config::configure_custom(meta, |virtual_cells, elem_1, elem_2, elem_3, q| {
+                let q = virtual_cells.query_selector(q);
+                let elem_1 = virtual_cells.query_advice(elem_1, Rotation::cur());
+                let elem_2 = virtual_cells.query_advice(elem_2, Rotation::cur());
+                let elem_3 = virtual_cells.query_advice(elem_3, Rotation::cur());
+                q * (Expression::Constant(Fp::from(2)) * elem_1 + elem_2 - elem_3)
+            })
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let output = layouter.assign_region(
+                || "custom recurrence",
+                |mut region| {
+                    let one = Value::known(Fp::one());
+                    let mut elem_1 = region.assign_advice(|| "elem_1", config.elem_1, 0, || one)?;
+                    let mut elem_2 = region.assign_advice(|| "elem_2", config.elem_2, 0, || one)?;
+                    for offset in 0..self.0 {
+                        config.q_fib.enable(&mut region, offset)?;
+                        let elem_3 = elem_1.value().copied() * Value::known(Fp::from(2))
+                            + elem_2.value().copied();
+                        let elem_3 =
+                            region.assign_advice(|| "elem_3", config.elem_3, offset, || elem_3)?;
+                        if offset + 1 == self.0 {
+                            return Ok(elem_3);
+                        }
+                        elem_1 = elem_2.copy_advice(
+                            || "elem_1",
+                            &mut region,
+                            config.elem_1,
+                            offset + 1,
+                        )?;
+                        elem_2 = elem_3.copy_advice(
+                            || "elem_2",
+                            &mut region,
+                            config.elem_2,
+                            offset + 1,
+                        )?;
+                    }
+                    Err(Error::Synthesis)
+                },
+            )?;
+            config.expose_public(layouter, &output, 0)
+        }
+    }
+
+    #[test]
+    fn test_custom_gate() {
+        // 1, 1, 3, 5, 11, 21
+        let prover = MockProver::run(5, &CustomGateCircuit(4), vec![vec![Fp::from(21)]]).unwrap();
+        prover.assert_satisfied();
+        let prover = MockProver::run(5, &CustomGateCircuit(4), vec![vec![Fp::from(8)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Extends a builder by each of `steps` in turn.
+    struct BuilderCircuit(Vec<usize>);
+
+    impl Circuit<Fp> for BuilderCircuit {
+        type Config = Config;
+
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0.clone())
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Self::Config::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let one = Value::known(Fp::one());
+            let mut builder = config.builder(layouter.namespace(|| "init"), one, one)?;
+            for &steps in &self.0 {
+                builder.extend(layouter.namespace(|| "extend"), steps)?;
+            }
+            config.expose_public(layouter, builder.output(), 0)
+        }
+    }
+
+    #[test]
+    fn test_builder_extends_incrementally() {
+        // the first row gives F(3), then 7 more steps reach F(10)
+        for steps in [vec![3, 4], vec![7]] {
+            let prover =
+                MockProver::run(5, &BuilderCircuit(steps), vec![vec![Fp::from(55)]]).unwrap();
+            prover.assert_satisfied();
+        }
+        let single = CircuitStats::measure(&BuilderCircuit(vec![7])).unwrap();
+        let split = CircuitStats::measure(&BuilderCircuit(vec![3, 4])).unwrap();
+        assert_eq!(single, split);
+    }
+
+    #[test]
+    fn test_assign_from_value_vec() {
+        let values = [1, 1, 2, 3, 5, 8, 13, 21, 34, 55].map(Fp::from).to_vec();
+        let circuit = ValueVecCircuit(Value::known(values));
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+        prover.assert_satisfied();
+
+        // keygen synthesizes without witnesses
+        let params: Params<EqAffine> = Params::new(5);
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+    }
+
+    #[test]
+    fn test_reference_check_passes_on_correct_witness() {
+        // Every row runs the debug reference check; a long run must not trip it.
+        let circuit = FibonacciCircuit::<Fp>::new(2, 5, 20);
+        let prover = MockProver::run(5, &circuit, vec![expected_instance(&circuit)]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "elem_3 of row 3 diverged from the reference sequence")]
+    fn test_reference_check_catches_wrong_witness() {
+        let circuit = FaultyCircuit(Checked(OffByOne(3)));
+        let _ = MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]);
+    }
+
+    #[test]
+    fn test_last_row() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        assert_eq!(
+            circuit.last_row(),
+            Some((Fp::from(21), Fp::from(34), Fp::from(55)))
+        );
+        assert_eq!(circuit.without_witnesses().last_row(), None);
+    }
+
+    #[test]
+    fn test_trace_table() {
+        // the table at the top of this module
+        let documented = "\
+| elem_1 | elem_2 | sum | q_fib
+--------------------------------
+|    1   |    1   |  2  |   1
+|    1   |    2   |  3  |   1
+|    2   |    3   |  5  |   1
+|        |        |     |   0
+";
+        assert_eq!(
+            FibonacciCircuit::<Fp>::new(1, 1, 5).trace_table(),
+            documented
+        );
+
+        let trace = FibonacciCircuit::<Fp>::new(1, 1, 20).trace_table();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines.len(), 2 + 18 + 1);
+        assert_eq!(lines[2], "|    1   |    1   |   2  |   1");
+        assert_eq!(lines[19], "|  2584  |  4181  | 6765 |   1");
+
+        let blank = FibonacciCircuit::<Fp>::new(1, 1, 5).without_witnesses();
+        assert!(blank
+            .trace_table()
+            .ends_with("|        |        |     |   1\n|        |        |     |   0\n"));
+    }
+
+    #[test]
+    fn test_builders() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 5);
+        assert_eq!(circuit.output(), Some(Fp::from(5)));
+        let circuit = circuit.with_num_terms(10);
+        assert_eq!(circuit.output(), Some(Fp::from(55)));
+        let circuit = circuit.with_seeds(2, 1);
+        assert_eq!(circuit.output(), Some(Fp::from(76)));
+    }
+
+    #[test]
+    fn test_expose_seeds() {
+        let circuit = FibonacciCircuit::<Fp>::new(3, 7, 8).expose_seeds(true);
+        let public_inputs = expected_instance(&circuit);
+        assert_eq!(public_inputs, vec![Fp::from(3), Fp::from(7), Fp::from(115)]);
+        assert_eq!(circuit.public_inputs(), Some(public_inputs.clone()));
+        circuit.verify_mock(5, &public_inputs).unwrap();
+
+        let err = circuit.verify_mock(5, &[Fp::from(115)]).unwrap_err();
+        assert!(matches!(err, FibError::BadPublicInputs(_)));
+        let forged = [Fp::from(3), Fp::from(8), Fp::from(115)];
+        assert!(matches!(
+            circuit.verify_mock(5, &forged),
+            Err(FibError::Unsatisfied(_))
+        ));
+    }
+
+    #[test]
+    fn test_halt_on_wraparound() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 1000).halt_on_wraparound();
+        let n = circuit.num_terms();
+        assert!(n < 1000);
+        assert_eq!(n, wraps_before(Fp::one(), Fp::one(), 1000));
+
+        // the next term would have wrapped
+        let (_, f_n_1, f_n) = circuit.last_row().unwrap();
+        assert!(f_n_1 + f_n < f_n);
+        assert_eq!(
+            FibonacciCircuit::<Fp>::new(1, 1, n + 1).output(),
+            Some(f_n_1 + f_n)
+        );
+
+        // a sequence that fits in the field is left untouched
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10).halt_on_wraparound();
+        assert_eq!(circuit.num_terms(), 10);
+        assert!(circuit.verify_mock(5, &[Fp::from(55)]).is_ok());
+    }
+
+    #[test]
+    fn test_u128_seeds() {
+        let big = u64::MAX as u128 + 1;
+        assert_eq!(fp_from_u128(big), Fp::from(u64::MAX) + Fp::one());
+        assert_eq!(fp_from_u128(7), Fp::from(7));
+        assert_eq!(fp_from_u128(u128::MAX), Fp::from_u128(u128::MAX));
+
+        let circuit = FibonacciCircuit::new_u128(big, big, 10);
+        assert_eq!(circuit.output(), Some(fp_from_u128(55 * big)));
+        let proof = crate::proof::prove(&circuit, 5).unwrap();
+        crate::proof::verify(&proof).unwrap();
+    }
+
+    #[test]
+    fn test_verify_mock_strict_rejects_unreferenced_inputs() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let extra = vec![Fp::from(55), Fp::from(99)];
+        let prover = MockProver::run(5, &circuit, vec![extra.clone()]).unwrap();
+        prover.assert_satisfied();
+
+        assert!(matches!(
+            circuit.verify_mock_strict(5, &extra),
+            Err(FibError::BadPublicInputs(_))
+        ));
+        circuit.verify_mock_strict(5, &[Fp::from(55)]).unwrap();
+
+        let circuit = circuit.expose_seeds(true);
+        circuit
+            .verify_mock_strict(5, &expected_instance(&circuit))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_fixed_term_count() {
+        let circuit = FixedFibonacciCircuit::<Fp, 10>::new(1, 1);
+        let terms = circuit.terms().unwrap();
+        assert_eq!(terms[9], Fp::from(55));
+        assert_eq!(terms.len(), 10);
+
+        let prover = MockProver::run(5, &circuit, vec![vec![terms[9]]]).unwrap();
+        prover.assert_satisfied();
+        let proof = crate::proof::prove(circuit.circuit(), min_k(10)).unwrap();
+        assert_eq!(proof.public_inputs, vec![terms[9]]);
+        crate::proof::verify(&proof).unwrap();
+    }
+
+    #[test]
+    fn test_instance_spec() {
+        use InstanceField::*;
+
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        assert_eq!(circuit.instance_spec(), vec![Output]);
+        let circuit = circuit.expose_seeds(true);
+        assert_eq!(circuit.instance_spec(), vec![SeedA, SeedB, Output]);
+
+        // boundary mode takes precedence over seeds-public mode
+        let circuit = circuit.expose_boundary(true);
+        assert_eq!(circuit.instance_spec(), vec![SeedA, SeedB, Term(9), Output]);
+        assert_eq!(circuit.public_inputs(), Some(expected_instance(&circuit)));
+    }
+
+    #[test]
+    fn test_missing_public_input() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let err = circuit.verify_mock(5, &[]).unwrap_err();
+        assert!(matches!(err, FibError::BadPublicInputs(_)));
+        assert_eq!(
+            err.to_string(),
+            "bad public inputs: expected 1 public input(s), got 0"
+        );
+        assert!(circuit.verify_mock(5, &[Fp::from(55)]).is_ok());
+    }
+
+    #[test]
+    fn test_tolerant_instance() {
+        let padded = [Fp::from(55), Fp::zero(), Fp::zero()];
+
+        // strict by default
+        let strict = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        assert!(matches!(
+            strict.verify_mock(5, &padded),
+            Err(FibError::BadPublicInputs(_))
+        ));
+
+        let tolerant = strict.clone().tolerant_instance(true);
+        tolerant.verify_mock(5, &padded).unwrap();
+        assert!(tolerant.verify_mock(5, &[]).is_err());
+        let err = tolerant
+            .verify_mock(5, &[Fp::from(55), Fp::zero(), Fp::one()])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "bad public inputs: instance row 2 is padding but not zero"
+        );
+
+        // zero padding leaves the instance commitment unchanged
+        let mut proof = crate::proof::prove(&strict, 5).unwrap();
+        proof.public_inputs = padded.to_vec();
+        crate::proof::verify_against(&tolerant, &proof).unwrap();
+        assert!(crate::proof::verify_against(&strict, &proof).is_err());
+    }
+
+    #[test]
+    fn test_no_public_output() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10)
+            .expose_seeds(true)
+            .no_public_output(true);
+        assert!(circuit.instance_spec().is_empty());
+        assert_eq!(CircuitStats::measure(&circuit).unwrap().instance_copies, 0);
+        circuit.verify_mock(5, &[]).unwrap();
+
+        let proof = crate::proof::prove(&circuit, 5).unwrap();
+        assert!(proof.public_inputs.is_empty());
+        crate::proof::verify(&proof).unwrap();
+        assert!(matches!(
+            crate::proof::verify_and_extract(&proof),
+            Err(FibError::BadPublicInputs(_))
+        ));
+
+        // stripping the output off a plain proof doesn't turn it into one of these
+        let mut stripped = crate::proof::prove(&FibonacciCircuit::new(1, 1, 10), 5).unwrap();
+        stripped.public_inputs.clear();
+        assert!(matches!(
+            crate::proof::verify(&stripped),
+            Err(FibError::Verify)
+        ));
+    }
+
+    #[test]
+    fn test_zero_seeds_rejected_by_default() {
+        let circuit = FibonacciCircuit::<Fp>::new(0, 0, 10);
+        let err = circuit.validate().unwrap_err();
+        assert!(matches!(err, FibError::BadPublicInputs(_)));
+
+        let circuit = circuit.allow_trivial(true);
+        assert!(circuit.validate().is_ok());
+        let prover = MockProver::run(5, &circuit, vec![expected_instance(&circuit)]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn test_too_few_terms_rejected() {
+        for num_terms in 0..3 {
+            let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms);
+            assert!(matches!(
+                circuit.validate(),
+                Err(FibError::BadPublicInputs(_))
+            ));
+            assert!(crate::proof::prove(&circuit, 5).is_err());
+        }
+        assert!(FibonacciCircuit::<Fp>::new(1, 1, 3).validate().is_ok());
+    }
+
+    #[test]
+    fn test_too_many_terms_rejected() {
+        let max = max_terms_for_k(MAX_SUPPORTED_K);
+        assert!(FibonacciCircuit::<Fp>::new(1, 1, max).validate().is_ok());
+        // the largest accepted circuit still gets a `k` that `Params::new` takes
+        assert_eq!(min_k(max), MAX_SUPPORTED_K);
+        assert_eq!(min_k(max + 1), MAX_SUPPORTED_K + 1);
+        for num_terms in [max + 1, usize::MAX] {
+            let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms);
+            assert!(matches!(
+                crate::proof::prove(&circuit, 5),
+                Err(FibError::TooManyTerms(n)) if n == num_terms
+            ));
+        }
+    }
+
+    /// Keeps the circuit `print_fibo` renders covered when `dev-graph` is off.
+    #[test]
+    fn test_render_circuit_builds() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        #[cfg(feature = "dev-graph")]
+        assert!(!halo2_proofs::dev::circuit_dot_graph(&circuit).is_empty());
+        #[cfg(not(feature = "dev-graph"))]
+        MockProver::run(5, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_trace_events_per_cell() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        /// Counts every event it sees.
+        struct Counter(Arc<AtomicUsize>);
+
+        impl tracing::Subscriber for Counter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        tracing::subscriber::with_default(Counter(count.clone()), || {
+            MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+        });
+        // three cells in each of the 8 rows; `SimpleFloorPlanner` runs every region
+        // twice, once to measure its shape
+        assert_eq!(count.load(Ordering::SeqCst), 2 * 3 * 8);
+    }
+
+    #[test]
+    fn test_seeds_from_le_bytes() {
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        let circuit = FibonacciCircuit::<Fp>::from_le_bytes(&[1; 32], &two, 10).unwrap();
+        let a = Fp::from_repr([1; 32]).unwrap();
+        assert_eq!(
+            circuit.output(),
+            FibonacciCircuit::from_seeds(a, Fp::from(2), 10).output()
+        );
+
+        // the modulus itself and the all-ones encoding are both out of range
+        let mut modulus = (-Fp::one()).to_repr();
+        modulus[0] += 1;
+        for bytes in [modulus, [0xff; 32]] {
+            assert!(matches!(
+                FibonacciCircuit::<Fp>::from_le_bytes(&two, &bytes, 10),
+                Err(FibError::BadPublicInputs(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_random_circuits_are_satisfied() {
+        use rand::rngs::SmallRng;
+        use rand::SeedableRng;
+
+        let mut rng = SmallRng::seed_from_u64(7);
+        for num_terms in [3, 10, 26] {
+            for _ in 0..4 {
+                let (circuit, public_inputs) = FibonacciCircuit::<Fp>::random(&mut rng, num_terms);
+                assert!(is_satisfied(&circuit, &public_inputs, min_k(num_terms)).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_satisfied() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        assert!(is_satisfied(&circuit, &[Fp::from(55)], 5).unwrap());
+        assert!(!is_satisfied(&circuit, &[Fp::from(56)], 5).unwrap());
+        assert!(matches!(
+            is_satisfied(&circuit, &[Fp::from(55)], 2),
+            Err(FibError::Synthesis(_))
+        ));
+    }
+
+    #[cfg(feature = "dev-graph")]
+    #[test]
+    fn print_fibo() {
+        use plotters::prelude::*;
+        let root = BitMapBackend::new("fib-layout.png", (1024, 3096)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root.titled("Fib Layout", ("sans-serif", 60)).unwrap();
+
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
         halo2_proofs::dev::CircuitLayout::default()
             .render(5, &circuit, &root)
             .unwrap();
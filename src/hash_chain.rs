@@ -0,0 +1,231 @@
+/*
+
+    Binds the whole sequence to one public value: after the sequence, every term is copied
+    next to a running hash
+
+    | elem_1 |  elem_2  |  elem_3  | q_start | q_hash
+    -------------------------------------------------
+    |   h_0  |   F(1)   |   h_1    |    1    |   1
+    |   h_1  |   F(2)   |   h_2    |    0    |   1
+    |  ...   |   ...    |   ...    |   ...   |  ...
+    | h_n-1  |   F(n)   |   h_n    |    0    |   1
+
+    q_start * h_0 = 0
+    q_hash * (elem_3 - (elem_1 + elem_2 + C)^5) = 0
+
+    h_i is copied from the elem_3 above it, F(i) from the sequence, and h_n is the only
+    public input.
+
+    The compression (h + x + C)^5 is a single MiMC-style round, chosen because x^5 is a
+    permutation of the Pasta fields and needs no extra columns. It is not collision
+    resistant; a real deployment would use the Poseidon chip from `halo2_gadgets`, which
+    this crate doesn't depend on.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::FibIter;
+
+/// The constant `C` added in every compression round.
+const ROUND_CONSTANT: u64 = 7;
+
+#[derive(Clone, Debug)]
+pub struct HashChainConfig {
+    pub(crate) fib: Config,
+    q_start: Selector,
+    q_hash: Selector,
+}
+
+impl HashChainConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let q_start = cs.selector();
+        let q_hash = cs.selector();
+
+        cs.create_gate("hash chain start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(q_start);
+            let h = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            vec![q_start * h]
+        });
+
+        cs.create_gate("hash chain step", |virtual_cells| {
+            let q_hash = virtual_cells.query_selector(q_hash);
+            let h = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let term = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let next = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let x = h + term + Expression::Constant(F::from(ROUND_CONSTANT));
+            let x5 = x.clone() * x.clone() * x.clone() * x.clone() * x;
+            vec![q_hash * (next - x5)]
+        });
+
+        Self {
+            fib,
+            q_start,
+            q_hash,
+        }
+    }
+
+    /// Lays out the running hash over `cells`, copying each in, and returns the cell
+    /// holding the digest.
+    pub(crate) fn assign_chain<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let fib = &self.fib;
+        layouter.assign_region(
+            || "hash chain",
+            |mut region| {
+                self.q_start.enable(&mut region, 0)?;
+                let mut h =
+                    region.assign_advice(|| "h_0", fib.elem_1, 0, || Value::known(F::zero()))?;
+                for (offset, term) in cells.iter().enumerate() {
+                    self.q_hash.enable(&mut region, offset)?;
+                    if offset > 0 {
+                        h = h.copy_advice(|| "h", &mut region, fib.elem_1, offset)?;
+                    }
+                    let term = term.copy_advice(|| "term", &mut region, fib.elem_2, offset)?;
+                    let next = h.value().zip(term.value()).map(|(&h, &t)| compress(h, t));
+                    h = region.assign_advice(|| "next h", fib.elem_3, offset, || next)?;
+                }
+                Ok(h)
+            },
+        )
+    }
+}
+
+/// One compression round, `(h + term + C)^5`.
+fn compress<F: FieldExt>(h: F, term: F) -> F {
+    let x = h + term + F::from(ROUND_CONSTANT);
+    x.square().square() * x
+}
+
+/// The running hash over `terms` starting from `h_0 = 0`, computed out of circuit.
+pub fn hash_chain<F: FieldExt>(terms: &[F]) -> F {
+    terms.iter().fold(F::zero(), |h, &term| compress(h, term))
+}
+
+/// Circuit proving knowledge of a Fibonacci sequence whose hash chain is the public input.
+#[derive(Clone, Debug)]
+pub struct HashChainCircuit<F: FieldExt> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> HashChainCircuit<F> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Result<Self, FibError> {
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "the hash chain needs at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        FibonacciCircuit::<F>::new(a, b, num_terms).validate()?;
+        Ok(Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            num_terms,
+        })
+    }
+
+    /// The sequence rows plus one hash row per term.
+    fn k(&self) -> u32 {
+        k_for_rows(self.num_terms - 2 + self.num_terms)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for HashChainCircuit<F> {
+    type Config = HashChainConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        HashChainConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.elem_1,
+            self.elem_2,
+            self.num_terms,
+        )?;
+
+        let digest = config.assign_chain(layouter.namespace(|| "hash chain"), &terms)?;
+
+        fib.expose_public(layouter, &digest, 0)
+    }
+}
+
+/// Proves knowledge of the `num_terms`-term sequence seeded with `a, b`, exposing only its
+/// hash chain.
+pub fn prove_hash_chain(a: u64, b: u64, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = HashChainCircuit::new(a, b, num_terms)?;
+    let terms: Vec<Fp> = FibIter::new(a, b).take(num_terms).collect();
+    let public_inputs = vec![hash_chain(&terms)];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_hash_chain`].
+pub fn verify_hash_chain(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = HashChainCircuit::<Fp>::new(0, 1, proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_chain_matches_reference() {
+        let terms: Vec<Fp> = [1, 1, 2, 3, 5, 8, 13, 21, 34, 55].map(Fp::from).to_vec();
+        let digest = hash_chain(&terms);
+        let proof = prove_hash_chain(1, 1, 10).unwrap();
+        assert_eq!(proof.public_inputs, vec![digest]);
+        verify_hash_chain(&proof).unwrap();
+
+        // changing any one term changes the hash
+        let mut tampered = terms;
+        tampered[0] = Fp::from(2);
+        let circuit = HashChainCircuit::<Fp>::new(1, 1, 10).unwrap();
+        let prover =
+            MockProver::run(circuit.k(), &circuit, vec![vec![hash_chain(&tampered)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        assert!(matches!(
+            HashChainCircuit::<Fp>::new(1, 1, 2),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+/*
+
+    Two sequences A and B of n terms each, and a third list C of 2n cells taking the terms
+    of A at even positions and of B at odd ones. Every cell of C is copied from its source,
+    and C is the public input:
+
+    | elem_1 | instance
+    -------------------
+    |  A(1)  |  A(1)
+    |  B(1)  |  B(1)
+    |  A(2)  |  A(2)
+    |  B(2)  |  B(2)
+    |  ...   |  ...
+
+    A and B are laid out by the plain chip, so C is not just any list of 2n values but
+    the interleaving of two sequences that each follow the recurrence.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+
+use crate::error::FibError;
+use crate::fibonacci::{known, Config, FibonacciCircuit};
+use crate::proof::k_for_rows;
+use crate::reference::FibIter;
+
+/// Circuit exposing the interleaving `[A(1), B(1), A(2), B(2), ...]` of the `num_terms`
+/// first terms of the sequences seeded with `a` and `b`.
+#[derive(Clone, Debug)]
+pub struct InterleaveCircuit<F: FieldExt> {
+    a: (Value<F>, Value<F>),
+    b: (Value<F>, Value<F>),
+    num_terms: usize,
+}
+
+impl<F: FieldExt> InterleaveCircuit<F> {
+    pub fn new(a: (u64, u64), b: (u64, u64), num_terms: usize) -> Result<Self, FibError> {
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        FibonacciCircuit::<F>::new(a.0, a.1, num_terms).validate()?;
+        FibonacciCircuit::<F>::new(b.0, b.1, num_terms).validate()?;
+        let known = |(x, y): (u64, u64)| (Value::known(F::from(x)), Value::known(F::from(y)));
+        Ok(Self {
+            a: known(a),
+            b: known(b),
+            num_terms,
+        })
+    }
+
+    /// The interleaved terms, which are also the public inputs, or `None` without
+    /// witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let terms = |(x, y): (Value<F>, Value<F>)| {
+            let (x, y) = (known(x)?, known(y)?);
+            let mut terms = vec![x, y];
+            while terms.len() < self.num_terms {
+                terms.push(terms[terms.len() - 2] + terms[terms.len() - 1]);
+            }
+            Some(terms)
+        };
+        let (a, b) = (terms(self.a)?, terms(self.b)?);
+        Some(a.into_iter().zip(b).flat_map(|(a, b)| [a, b]).collect())
+    }
+
+    /// The smallest `k` fitting both sequences and one row per interleaved term.
+    pub fn k(&self) -> u32 {
+        k_for_rows(2 * (self.num_terms - 2) + 2 * self.num_terms)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for InterleaveCircuit<F> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: (Value::unknown(), Value::unknown()),
+            b: (Value::unknown(), Value::unknown()),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Config::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let a = config.assign_terms(
+            layouter.namespace(|| "sequence A"),
+            self.a.0,
+            self.a.1,
+            self.num_terms,
+        )?;
+        let b = config.assign_terms(
+            layouter.namespace(|| "sequence B"),
+            self.b.0,
+            self.b.1,
+            self.num_terms,
+        )?;
+
+        let interleaved = layouter.assign_region(
+            || "interleaved",
+            |mut region| {
+                let sources = a.iter().zip(&b).flat_map(|(a, b)| [a, b]);
+                sources
+                    .enumerate()
+                    .map(|(offset, source)| {
+                        source.copy_advice(|| "term", &mut region, config.elem_1, offset)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        for (row, cell) in interleaved.iter().enumerate() {
+            config.expose_public(layouter.namespace(|| "interleaved term"), cell, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// The interleaving of the first `num_terms` terms of the sequences seeded with `a` and
+/// `b`, computed out of circuit.
+pub fn interleave<F: FieldExt>(a: (u64, u64), b: (u64, u64), num_terms: usize) -> Vec<F> {
+    FibIter::<F>::new(a.0, a.1)
+        .zip(FibIter::<F>::new(b.0, b.1))
+        .take(num_terms)
+        .flat_map(|(a, b)| [a, b])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_interleave_fibonacci_and_lucas() {
+        // 1, 1, 2, 3, 5 and 2, 1, 3, 4, 7
+        let circuit = InterleaveCircuit::<Fp>::new((1, 1), (2, 1), 5).unwrap();
+        let public_inputs = circuit.public_inputs().unwrap();
+        let expected: Vec<Fp> = [1, 2, 1, 1, 2, 3, 3, 4, 5, 7].map(Fp::from).to_vec();
+        assert_eq!(public_inputs, expected);
+        assert_eq!(interleave::<Fp>((1, 1), (2, 1), 5), expected);
+
+        let prover = MockProver::run(circuit.k(), &circuit, vec![public_inputs]).unwrap();
+        prover.assert_satisfied();
+
+        // B first is a different interleaving
+        let swapped = interleave::<Fp>((2, 1), (1, 1), 5);
+        let prover = MockProver::run(circuit.k(), &circuit, vec![swapped]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
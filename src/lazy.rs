@@ -0,0 +1,132 @@
+//! Defers the seeds to a closure called during synthesis, for witnesses fetched from an
+//! external source only once a proof is actually being made.
+
+use std::cell::OnceCell;
+use std::fmt;
+use std::rc::Rc;
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+
+use crate::fibonacci::Config;
+
+/// A [`FibonacciCircuit`] whose seeds come from a closure; built by
+/// [`FibonacciCircuit::lazy`].
+///
+/// The seeds are assigned in a row of their own, and the sequence is copied on from them.
+/// The closure is called when those two cells get their values, at most once per
+/// synthesis. Keygen lays the cells out without evaluating them, so it never calls the
+/// closure, even on the circuit itself.
+///
+/// [`FibonacciCircuit`]: crate::FibonacciCircuit
+/// [`FibonacciCircuit::lazy`]: crate::FibonacciCircuit::lazy
+#[derive(Clone)]
+pub struct LazyCircuit<F: FieldExt> {
+    seed_fn: Option<Rc<dyn Fn() -> (F, F)>>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> LazyCircuit<F> {
+    pub(crate) fn new(seed_fn: impl Fn() -> (F, F) + 'static, num_terms: usize) -> Self {
+        Self {
+            seed_fn: Some(Rc::new(seed_fn)),
+            num_terms,
+        }
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.num_terms
+    }
+}
+
+impl<F: FieldExt> fmt::Debug for LazyCircuit<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyCircuit")
+            .field("num_terms", &self.num_terms)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for LazyCircuit<F> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            seed_fn: None,
+            num_terms: self.num_terms,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Config::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let seeds = OnceCell::new();
+        let seed = |pick: fn((F, F)) -> F| match &self.seed_fn {
+            Some(seed_fn) => Value::known(pick(*seeds.get_or_init(|| seed_fn()))),
+            None => Value::unknown(),
+        };
+        let (elem_1, elem_2) = layouter.assign_region(
+            || "seeds",
+            |mut region| {
+                let elem_1 =
+                    region.assign_advice(|| "elem_1", config.elem_1, 0, || seed(|(a, _)| a))?;
+                let elem_2 =
+                    region.assign_advice(|| "elem_2", config.elem_2, 0, || seed(|(_, b)| b))?;
+                Ok((elem_1, elem_2))
+            },
+        )?;
+
+        // every row copies its first two cells from the one before, starting at the seeds
+        let (mut elem_2, mut elem_3) = (elem_1, elem_2);
+        for _ in 0..self.num_terms.saturating_sub(2).max(1) {
+            (elem_2, elem_3) =
+                config.assign(layouter.namespace(|| "next row"), &elem_2, &elem_3)?;
+        }
+        config.expose_public(layouter, &elem_3, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use halo2_proofs::pasta::{EqAffine, Fp};
+    use halo2_proofs::poly::commitment::Params;
+
+    use super::*;
+    use crate::fibonacci::FibonacciCircuit;
+    use crate::proof::{create_circuit_proof, verify_circuit_proof};
+
+    #[test]
+    fn test_seed_fn_called_only_when_proving() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let circuit = FibonacciCircuit::<Fp>::lazy(
+            move || {
+                counter.set(counter.get() + 1);
+                (Fp::one(), Fp::one())
+            },
+            10,
+        );
+
+        let params: Params<EqAffine> = Params::new(5);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        keygen_pk(&params, vk, &circuit).unwrap();
+        assert_eq!(calls.get(), 0);
+
+        let public_inputs = [Fp::from(55)];
+        let proof = create_circuit_proof(&circuit, &public_inputs, 5).unwrap();
+        assert_eq!(calls.get(), 1);
+        verify_circuit_proof(&circuit, &public_inputs, &proof, 5).unwrap();
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+/*
+
+    The copy-lane layout plus a row index, so the claimed number of terms is bound to
+    the number of rows actually laid out:
+
+    | elem_1 | elem_2 | elem_3 | index | q_shift | instance
+    --------------------------------------------------------
+    |    1   |    1   |    2   |   3   |    1    |  F(n)
+    |    1   |    2   |    3   |   4   |    1    |   n
+    |  ...   |  ...   |  ...   |  ...  |   ...   |
+    | F(n-2) | F(n-1) |  F(n)  |   n   |    0    |
+
+    q_shift * (index(next) - index - 1) = 0
+
+    The first index is pinned to 3 and the last one is copied to the instance.
+
+*/
+
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::copy_lane::CopyLaneConfig;
+use crate::error::FibError;
+use crate::fibonacci::{min_k, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct LengthConfig {
+    pub(crate) lane: CopyLaneConfig,
+    pub(crate) index: Column<Advice>,
+}
+
+impl LengthConfig {
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+        let lane = CopyLaneConfig::configure(cs);
+        let index = cs.advice_column();
+        cs.enable_equality(index);
+        // pins the first index
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+
+        cs.create_gate("row index", |virtual_cells| {
+            let q_shift = virtual_cells.query_selector(lane.q_shift);
+            let index_next = virtual_cells.query_advice(index, Rotation::next());
+            let index = virtual_cells.query_advice(index, Rotation::cur());
+            vec![q_shift * (index_next - index - Expression::Constant(F::one()))]
+        });
+
+        Self { lane, index }
+    }
+}
+
+/// Circuit exposing `[F(n), n]`: the output together with the claimed number of terms.
+#[derive(Clone, Debug)]
+pub struct LengthCircuit<F: Field> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> LengthCircuit<F> {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Result<Self, FibError> {
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        Ok(Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            num_terms,
+        })
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for LengthCircuit<F> {
+    type Config = LengthConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        LengthConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let rows = self.num_terms - 2;
+        let (output, length) = layouter.assign_region(
+            || "sequence",
+            |mut region| {
+                let output =
+                    config
+                        .lane
+                        .assign_rows(&mut region, self.elem_1, self.elem_2, rows)?;
+
+                let mut length = None;
+                for offset in 0..rows {
+                    let index = Value::known(F::from((offset + 3) as u64));
+                    let cell = region.assign_advice(|| "index", config.index, offset, || index)?;
+                    if offset == 0 {
+                        region.constrain_constant(cell.cell(), F::from(3))?;
+                    }
+                    length = Some(cell);
+                }
+                Ok((output, length.expect("at least one row")))
+            },
+        )?;
+
+        layouter.constrain_instance(output.cell(), config.lane.instance, 0)?;
+        layouter.constrain_instance(length.cell(), config.lane.instance, 1)
+    }
+}
+
+/// Proves the `num_terms`-th term together with `num_terms` itself.
+pub fn prove_with_length(a: u64, b: u64, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = LengthCircuit::<Fp>::new(a, b, num_terms)?;
+    let output = FibonacciCircuit::<Fp>::new(a, b, num_terms)
+        .output()
+        .expect("seeds are known");
+    let public_inputs = vec![output, Fp::from(num_terms as u64)];
+    let k = min_k(num_terms);
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_with_length`].
+pub fn verify_with_length(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = LengthCircuit::<Fp>::new(0, 0, proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claimed_length() {
+        let proof = prove_with_length(1, 1, 10).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(55), Fp::from(10)]);
+        verify_with_length(&proof).unwrap();
+
+        let mut longer = proof.clone();
+        longer.public_inputs[1] = Fp::from(11);
+        assert!(matches!(verify_with_length(&longer), Err(FibError::Verify)));
+
+        let mut shorter = proof;
+        shorter.public_inputs[1] = Fp::from(9);
+        assert!(matches!(
+            verify_with_length(&shorter),
+            Err(FibError::Verify)
+        ));
+    }
+}
@@ -1 +1,98 @@
-mod fibonacci;
+pub mod battery;
+pub mod binet;
+pub mod bit_length;
+pub mod bounded;
+pub mod budget;
+pub mod calldata;
+pub mod cassini;
+pub mod constant;
+pub mod copy_lane;
+pub mod crt;
+pub mod derived;
+pub mod divides;
+pub mod error;
+pub mod extension;
+pub mod fibonacci;
+pub mod gcd;
+pub mod hash_chain;
+pub mod identity;
+pub mod interleave;
+pub mod lazy;
+pub mod length;
+pub mod membership;
+pub mod modular;
+pub mod observer;
+pub mod packed;
+pub mod padded;
+pub mod params;
+pub mod parity;
+pub mod prime;
+pub mod proof;
+pub mod ranged_window;
+pub mod ratio;
+pub mod recurrence;
+pub mod reference;
+pub mod relation;
+pub mod segments;
+pub mod shuffle;
+pub mod signed;
+pub mod squares;
+pub mod stats;
+#[cfg(feature = "transcript-dump")]
+pub mod transcript;
+pub mod transform;
+pub mod window;
+pub mod zeckendorf;
+
+pub use battery::FibonacciBattery;
+pub use binet::prove_binet;
+pub use bit_length::prove_bit_length;
+pub use bounded::prove_bounded;
+pub use budget::{BudgetConfig, BudgetedCircuit};
+pub use calldata::proof_to_calldata;
+pub use cassini::prove_cassini;
+pub use constant::ConstantOutputCircuit;
+pub use copy_lane::CopyLaneCircuit;
+pub use crt::prove_crt;
+pub use derived::prove_from_hash;
+pub use divides::prove_divides;
+pub use error::{describe_failure, FibError};
+pub use extension::prove_extension;
+pub use fibonacci::{
+    compute_witness, fp_from_u128, is_satisfied, max_terms_for_k, min_k, Config, Equality,
+    FibonacciBuilder, FibonacciCircuit, FixedFibonacciCircuit, InstanceField,
+};
+pub use gcd::prove_gcd_identity;
+pub use hash_chain::prove_hash_chain;
+pub use identity::prove_identity;
+pub use interleave::InterleaveCircuit;
+pub use lazy::LazyCircuit;
+pub use length::prove_with_length;
+pub use membership::prove_membership;
+pub use modular::prove_mod;
+pub use observer::ObservedCircuit;
+pub use packed::prove_packed;
+pub use padded::PaddedCircuit;
+pub use params::FibParams;
+pub use parity::prove_parity;
+pub use prime::prove_fib_prime_property;
+pub use proof::{
+    autotune_k, params_k, prove, prove_with_params, prove_with_rng, prove_with_stats, read_params,
+    verify, verify_against, verify_and_extract, verify_batch, verify_with_params, vk_fingerprint,
+    write_params, FibonacciProof, ProofWithStats,
+};
+pub use ranged_window::RangedWindowCircuit;
+pub use ratio::prove_ratio_bound;
+pub use recurrence::RecurrenceCircuit;
+pub use relation::SeedRelationCircuit;
+pub use segments::{prove_continuation, prove_segments, verify_segments};
+pub use shuffle::prove_shuffle;
+pub use signed::SignedCircuit;
+pub use squares::prove_sum_of_squares;
+pub use stats::{
+    active_selector_rows, advice_columns, assigned_cell_count, constraint_system_info, copy_count,
+    estimate_cost, permutation_columns, CircuitStats, CostEstimate, ProvingTimeClass,
+};
+pub use transform::TransformedCircuit;
+pub use window::WindowCircuit;
+pub use zeckendorf::prove_zeckendorf;
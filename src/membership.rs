@@ -0,0 +1,199 @@
+/*
+
+    "target is one of F(0), F(1), ..., F(n)" via a lookup argument
+
+    | target | q_member |      | table |
+    ---------------------      ---------
+    |   13   |    1     |      |   0   |
+                               |   1   |
+                               |   1   |
+                               |   2   |
+                               |  ...  |
+                               |  F(n) |
+
+    q_member * target ∈ table
+
+    F(0) = 0 is part of the table because rows with q_member off look up 0.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct MembershipConfig {
+    target: Column<Advice>,
+    table: TableColumn,
+    q_member: Selector,
+    instance: Column<Instance>,
+}
+
+/// Configures the lookup of a target against the precomputed sequence table.
+pub fn configure_lookup<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> MembershipConfig {
+    let target = cs.advice_column();
+    cs.enable_equality(target);
+    let instance = cs.instance_column();
+    cs.enable_equality(instance);
+    let table = cs.lookup_table_column();
+    let q_member = cs.complex_selector();
+
+    cs.lookup(|virtual_cells| {
+        let q_member = virtual_cells.query_selector(q_member);
+        let target = virtual_cells.query_advice(target, Rotation::cur());
+        vec![(q_member * target, table)]
+    });
+
+    MembershipConfig {
+        target,
+        table,
+        q_member,
+        instance,
+    }
+}
+
+/// Circuit proving that the public target is one of the first `num_terms` terms of the
+/// standard `1, 1` sequence.
+#[derive(Clone, Debug)]
+pub struct MembershipCircuit {
+    target: u64,
+    num_terms: usize,
+    witness: bool,
+}
+
+impl MembershipCircuit {
+    pub fn new(target: u64, num_terms: usize) -> Self {
+        Self {
+            target,
+            num_terms,
+            witness: true,
+        }
+    }
+
+    fn k(&self) -> u32 {
+        // F(0)..=F(n), plus the row the table is padded from
+        k_for_rows(self.num_terms + 2)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MembershipCircuit {
+    type Config = MembershipConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        configure_lookup(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "fibonacci table",
+            |mut table| {
+                let (mut a, mut b) = (F::zero(), F::one());
+                for offset in 0..=self.num_terms {
+                    table.assign_cell(|| "F(i)", config.table, offset, || Value::known(a))?;
+                    (a, b) = (b, a + b);
+                }
+                Ok(())
+            },
+        )?;
+
+        let target = if self.witness {
+            Value::known(F::from(self.target))
+        } else {
+            Value::unknown()
+        };
+        let cell = layouter.assign_region(
+            || "membership",
+            |mut region| {
+                config.q_member.enable(&mut region, 0)?;
+                region.assign_advice(|| "target", config.target, 0, || target)
+            },
+        )?;
+        layouter.constrain_instance(cell.cell(), config.instance, 0)
+    }
+}
+
+/// Proves that `target` is one of the first `num_terms` Fibonacci numbers; the target is
+/// the public input.
+pub fn prove_membership(target: u64, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let in_sequence = (0..=num_terms)
+        .scan((0u128, 1u128), |state, _| {
+            let term = state.0;
+            *state = (state.1, state.0 + state.1);
+            Some(term)
+        })
+        // the terms pass any u64 long before they'd overflow a u128
+        .take_while(|&term| term <= target as u128)
+        .any(|term| term == target as u128);
+    if !in_sequence {
+        return Err(FibError::BadPublicInputs(format!(
+            "{} is not among the first {} terms",
+            target, num_terms
+        )));
+    }
+
+    let circuit = MembershipCircuit::new(target, num_terms);
+    let public_inputs = vec![Fp::from(target)];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_membership`].
+pub fn verify_membership(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = MembershipCircuit::new(0, proof.num_terms);
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::{MockProver, VerifyFailure};
+
+    use super::*;
+
+    #[test]
+    fn test_13_is_a_member() {
+        let proof = prove_membership(13, 15).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(13)]);
+        verify_membership(&proof).unwrap();
+    }
+
+    #[test]
+    fn test_non_member_fails_lookup() {
+        assert!(prove_membership(14, 15).is_err());
+        // past the 186th term the scan would overflow a u128 if it didn't stop at the target
+        assert!(matches!(
+            prove_membership(u64::MAX, 300),
+            Err(FibError::BadPublicInputs(_))
+        ));
+
+        let circuit = MembershipCircuit::new(14, 15);
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(14)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|failure| matches!(failure, VerifyFailure::Lookup { .. })));
+    }
+}
@@ -0,0 +1,395 @@
+/*
+
+    The sequence reduced mod a small public modulus m, e.g. mod 10:
+
+    1, 1, 2, 3, 5, 8, 3, 1, 4, 5, 9, 4, ...
+
+    | elem_1 | elem_2 | elem_3 | carry | modulus | q_mod
+    ----------------------------------------------------
+    |    1   |    1   |    2   |   0   |    10   |   1
+    |    1   |    2   |    3   |   0   |    10   |   1
+    |   ...  |   ...  |   ...  |  ...  |   ...   |  ...
+    |    5   |    8   |    3   |   1   |    10   |   1
+
+    q_mod * (elem_1 + elem_2 - carry * modulus - elem_3) = 0
+    q_mod * carry * (1 - carry) = 0
+
+    and (modulus, elem_i) are looked up in a table of (m, 0..m) pairs, so every residue is
+    < m. The table can hold several moduli, letting sequences mod different moduli share
+    the columns.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{max_terms_for_k, Config, MAX_SUPPORTED_K};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::fib_mod;
+
+#[derive(Clone, Debug)]
+pub struct ModularConfig {
+    fib: Config,
+    carry: Column<Advice>,
+    modulus: Column<Fixed>,
+    moduli: TableColumn,
+    residues: TableColumn,
+    q_mod: Selector,
+}
+
+impl ModularConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        // pins the seeds of a standard sequence
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let carry = cs.advice_column();
+        let modulus = cs.fixed_column();
+        let moduli = cs.lookup_table_column();
+        let residues = cs.lookup_table_column();
+        let q_mod = cs.complex_selector();
+
+        cs.create_gate("fibonacci mod m", |virtual_cells| {
+            let q_mod = virtual_cells.query_selector(q_mod);
+            let elem_1 = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let elem_2 = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let elem_3 = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let carry = virtual_cells.query_advice(carry, Rotation::cur());
+            let modulus = virtual_cells.query_fixed(modulus, Rotation::cur());
+            let one = Expression::Constant(F::one());
+
+            vec![
+                q_mod.clone() * (elem_1 + elem_2 - carry.clone() * modulus - elem_3),
+                q_mod * carry.clone() * (one - carry),
+            ]
+        });
+
+        for column in [fib.elem_1, fib.elem_2, fib.elem_3] {
+            cs.lookup(|virtual_cells| {
+                let q_mod = virtual_cells.query_selector(q_mod);
+                let modulus = virtual_cells.query_fixed(modulus, Rotation::cur());
+                let residue = virtual_cells.query_advice(column, Rotation::cur());
+                vec![
+                    (q_mod.clone() * modulus, moduli),
+                    (q_mod * residue, residues),
+                ]
+            });
+        }
+
+        Self {
+            fib,
+            carry,
+            modulus,
+            moduli,
+            residues,
+            q_mod,
+        }
+    }
+
+    /// Loads the `(m, r)` pairs for every `m` in `moduli`, after a `(0, 0)` row matching the
+    /// lookup inputs of rows with `q_mod` off.
+    pub(crate) fn load_table<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        moduli: &[u64],
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "residues",
+            |mut table| {
+                let pairs = moduli.iter().flat_map(|&m| (0..m).map(move |r| (m, r)));
+                for (row, (m, r)) in std::iter::once((0, 0)).chain(pairs).enumerate() {
+                    table.assign_cell(
+                        || "modulus",
+                        self.moduli,
+                        row,
+                        || Value::known(F::from(m)),
+                    )?;
+                    table.assign_cell(
+                        || "residue",
+                        self.residues,
+                        row,
+                        || Value::known(F::from(r)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Lays out `residues` (a reduced sequence, seeds first) mod `modulus` and returns the
+    /// cells of every term. Unknown residues assign unknown cells, as keygen needs.
+    pub(crate) fn assign_sequence<F: FieldExt>(
+        &self,
+        layouter: impl Layouter<F>,
+        residues: Value<&[u64]>,
+        num_terms: usize,
+        modulus: u64,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        self.assign_sequence_with(layouter, residues, num_terms, modulus, false)
+    }
+
+    /// [`Self::assign_sequence`] with both seeds pinned to 1, for circuits that don't expose
+    /// them.
+    pub(crate) fn assign_standard_sequence<F: FieldExt>(
+        &self,
+        layouter: impl Layouter<F>,
+        residues: Value<&[u64]>,
+        num_terms: usize,
+        modulus: u64,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        self.assign_sequence_with(layouter, residues, num_terms, modulus, true)
+    }
+
+    fn assign_sequence_with<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        residues: Value<&[u64]>,
+        num_terms: usize,
+        modulus: u64,
+        pin_seeds: bool,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let witness = |i: usize| residues.map(|residues| F::from(residues[i]));
+        layouter.assign_region(
+            || "fibonacci mod m",
+            |mut region| {
+                let mut cells: Vec<AssignedCell<F, F>> = vec![
+                    region.assign_advice(|| "elem_1", self.fib.elem_1, 0, || witness(0))?,
+                    region.assign_advice(|| "elem_2", self.fib.elem_2, 0, || witness(1))?,
+                ];
+                if pin_seeds {
+                    region.constrain_constant(cells[0].cell(), F::one())?;
+                    region.constrain_constant(cells[1].cell(), F::one())?;
+                }
+                for offset in 0..num_terms - 2 {
+                    self.q_mod.enable(&mut region, offset)?;
+                    region.assign_fixed(
+                        || "modulus",
+                        self.modulus,
+                        offset,
+                        || Value::known(F::from(modulus)),
+                    )?;
+                    if offset > 0 {
+                        cells[offset].copy_advice(
+                            || "copy elem_2 into current elem_1",
+                            &mut region,
+                            self.fib.elem_1,
+                            offset,
+                        )?;
+                        cells[offset + 1].copy_advice(
+                            || "copy elem_3 into current elem_2",
+                            &mut region,
+                            self.fib.elem_2,
+                            offset,
+                        )?;
+                    }
+                    let carry = residues.map(|residues| {
+                        F::from((residues[offset] + residues[offset + 1]) / modulus)
+                    });
+                    region.assign_advice(|| "carry", self.carry, offset, || carry)?;
+                    let elem_3 = region.assign_advice(
+                        || "elem_3",
+                        self.fib.elem_3,
+                        offset,
+                        || witness(offset + 2),
+                    )?;
+                    cells.push(elem_3);
+                }
+                Ok(cells)
+            },
+        )
+    }
+
+    pub(crate) fn expose_public<F: FieldExt>(
+        &self,
+        layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        self.fib.expose_public(layouter, cell, row)
+    }
+}
+
+/// Circuit proving the first `num_terms` terms of the sequence seeded with `a, b`, reduced
+/// mod `modulus`, and exposing all of them as public inputs.
+#[derive(Clone, Debug)]
+pub struct ModularCircuit {
+    a: u64,
+    b: u64,
+    num_terms: usize,
+    modulus: u64,
+    witness: bool,
+}
+
+impl ModularCircuit {
+    /// Largest supported modulus; the residue table needs one row per value.
+    pub const MAX_MODULUS: u64 = 1 << 16;
+
+    pub fn new(a: u64, b: u64, num_terms: usize, modulus: u64) -> Result<Self, FibError> {
+        if !(2..=Self::MAX_MODULUS).contains(&modulus) {
+            return Err(FibError::BadPublicInputs(format!(
+                "modulus must be in 2..={}, got {}",
+                Self::MAX_MODULUS,
+                modulus
+            )));
+        }
+        if a >= modulus || b >= modulus {
+            return Err(FibError::BadPublicInputs(format!(
+                "seeds ({}, {}) must already be reduced mod {}",
+                a, b, modulus
+            )));
+        }
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        if num_terms > max_terms_for_k(MAX_SUPPORTED_K) {
+            return Err(FibError::TooManyTerms(num_terms));
+        }
+        Ok(Self {
+            a,
+            b,
+            num_terms,
+            modulus,
+            witness: true,
+        })
+    }
+
+    /// The reduced sequence, which is also the public input.
+    pub fn residues(&self) -> Vec<u64> {
+        fib_mod(self.a, self.b, self.num_terms, self.modulus)
+    }
+
+    fn k(&self) -> u32 {
+        // the (0, 0) row, the residues, and the padding from the row after the last value
+        k_for_rows((self.num_terms - 2).max(self.modulus as usize + 2))
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ModularCircuit {
+    type Config = ModularConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ModularConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.load_table(layouter.namespace(|| "table"), &[self.modulus])?;
+
+        // the seeds as given, so an unreduced one is caught by the lookup
+        let mut residues = self.residues();
+        (residues[0], residues[1]) = (self.a, self.b);
+        let residues = if self.witness {
+            Value::known(residues.as_slice())
+        } else {
+            Value::unknown()
+        };
+        let cells = config.assign_sequence(
+            layouter.namespace(|| "sequence"),
+            residues,
+            self.num_terms,
+            self.modulus,
+        )?;
+
+        for (row, cell) in cells.iter().enumerate() {
+            config.expose_public(layouter.namespace(|| "residue"), cell, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proves the first `num_terms` terms of the sequence mod `modulus`.
+pub fn prove_mod(
+    a: u64,
+    b: u64,
+    num_terms: usize,
+    modulus: u64,
+) -> Result<FibonacciProof, FibError> {
+    let circuit = ModularCircuit::new(a, b, num_terms, modulus)?;
+    let public_inputs: Vec<Fp> = circuit.residues().into_iter().map(Fp::from).collect();
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_mod`] for the same modulus.
+pub fn verify_mod(modulus: u64, proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = ModularCircuit::new(0, 0, proof.num_terms, modulus)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::{MockProver, VerifyFailure};
+
+    use super::*;
+
+    #[test]
+    fn test_fib_mod_10() {
+        let proof = prove_mod(1, 1, 15, 10).unwrap();
+        let expected = [1, 1, 2, 3, 5, 8, 3, 1, 4, 5, 9, 4, 3, 7, 0];
+        assert_eq!(
+            proof.public_inputs,
+            expected.iter().map(|&r| Fp::from(r)).collect::<Vec<_>>()
+        );
+        verify_mod(10, &proof).unwrap();
+    }
+
+    #[test]
+    fn test_too_many_terms_rejected() {
+        let num_terms = max_terms_for_k(MAX_SUPPORTED_K) + 1;
+        assert!(matches!(
+            ModularCircuit::new(1, 1, num_terms, 10),
+            Err(FibError::TooManyTerms(n)) if n == num_terms
+        ));
+    }
+
+    #[test]
+    fn test_wrong_residue_fails() {
+        let circuit = ModularCircuit::new(5, 8, 3, 10).unwrap();
+        let public_inputs = vec![Fp::from(5), Fp::from(8), Fp::from(13)];
+        let prover = MockProver::run(circuit.k(), &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_unreduced_seed_fails_lookup() {
+        // bypass the constructor check to witness a seed that is not a residue mod 10
+        let circuit = ModularCircuit {
+            a: 5,
+            b: 12,
+            num_terms: 3,
+            modulus: 10,
+            witness: true,
+        };
+        let public_inputs = vec![Fp::from(5), Fp::from(12), Fp::from(7)];
+        let prover = MockProver::run(circuit.k(), &circuit, vec![public_inputs]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .any(|failure| matches!(failure, VerifyFailure::Lookup { .. })));
+    }
+}
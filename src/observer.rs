@@ -0,0 +1,103 @@
+//! Reports every computed term to a callback during synthesis, e.g. to drive a progress
+//! bar or a live demo. The layout is the plain chip's; the observer only watches it.
+
+use std::fmt;
+use std::rc::Rc;
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner};
+use halo2_proofs::plonk::*;
+
+use crate::fibonacci::{known, Config, FibonacciCircuit};
+
+/// A [`FibonacciCircuit`] that calls its observer with `(row, elem_3)` for every row it lays
+/// out; built by [`FibonacciCircuit::with_observer`].
+///
+/// The observer runs once per synthesis, after the sequence is laid out, and only when the
+/// values are known, so keygen (which synthesizes without witnesses) never calls it.
+#[derive(Clone)]
+pub struct ObservedCircuit<F: FieldExt> {
+    circuit: FibonacciCircuit<F>,
+    observer: Rc<dyn Fn(usize, F)>,
+}
+
+impl<F: FieldExt> ObservedCircuit<F> {
+    pub(crate) fn new(circuit: FibonacciCircuit<F>, observer: Box<dyn Fn(usize, F)>) -> Self {
+        Self {
+            circuit,
+            observer: Rc::from(observer),
+        }
+    }
+}
+
+impl<F: FieldExt> fmt::Debug for ObservedCircuit<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedCircuit")
+            .field("circuit", &self.circuit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ObservedCircuit<F> {
+    type Config = Config;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            observer: self.observer.clone(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Config::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let terms = self.circuit.synthesize_at(config, layouter, 0)?;
+        // terms[row + 2] is the elem_3 of row `row`
+        for (row, term) in terms.iter().skip(2).enumerate() {
+            if let Some(&elem_3) = known(term.value()) {
+                (self.observer)(row, elem_3);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::{EqAffine, Fp};
+    use halo2_proofs::poly::commitment::Params;
+
+    use super::*;
+
+    #[test]
+    fn test_observer_sees_every_row() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = seen.clone();
+        let circuit =
+            FibonacciCircuit::<Fp>::new(1, 1, 10).with_observer(Box::new(move |row, elem_3| {
+                sink.borrow_mut().push((row, elem_3))
+            }));
+
+        // keygen lays out the circuit without witnesses, and observes nothing
+        let params: Params<EqAffine> = Params::new(5);
+        keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        assert!(seen.borrow().is_empty());
+
+        MockProver::run(5, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+        let expected: Vec<(usize, Fp)> = [2, 3, 5, 8, 13, 21, 34, 55]
+            .into_iter()
+            .enumerate()
+            .map(|(row, term)| (row, Fp::from(term)))
+            .collect();
+        assert_eq!(*seen.borrow(), expected);
+    }
+}
@@ -0,0 +1,198 @@
+/*
+
+    Both seeds in one public input, packed as a * 2^32 + b. The packed value is decomposed
+    into 64 bits as in the parity layout, so the running sum after the first 32 bits is a,
+    and one more row recovers b:
+
+    | elem_1 | elem_2 | elem_3 | q_bits | q_unpack | instance
+    ---------------------------------------------------------
+    |  b_63  |  acc_0 |        |   1    |    0     |  packed
+    |  ...   |  ...   |        |  ...   |   ...    |   F(n)
+    |  b_32  | acc_31 |        |   1    |    0     |
+    |  ...   |  ...   |        |  ...   |   ...    |
+    |  b_0   | acc_63 |        |   0    |    0     |
+    | packed |    a   |    b   |   0    |    1     |
+
+    q_unpack * (packed - 2^32 * a - b) = 0
+
+    acc_63 is the packed value and goes to the instance; acc_31 is a, and is copied into
+    the unpacking row together with the seed cells of the sequence. The decomposition puts
+    a below 2^32 and makes b the sum of the low 32 bits, so below 2^32 too: no other pair
+    of seeds packs to the same value.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::FibonacciCircuit;
+use crate::parity::{ParityConfig, BITS};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+/// `a * 2^32 + b`.
+pub fn pack_seeds(a: u32, b: u32) -> u64 {
+    (u64::from(a) << 32) | u64::from(b)
+}
+
+#[derive(Clone, Debug)]
+pub struct PackedConfig {
+    bits: ParityConfig,
+    q_unpack: Selector,
+}
+
+impl PackedConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let bits = ParityConfig::configure(cs);
+        let fib = bits.fib;
+        let q_unpack = cs.selector();
+
+        cs.create_gate("unpack", |virtual_cells| {
+            let q_unpack = virtual_cells.query_selector(q_unpack);
+            let packed = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let a = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let b = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let shift = Expression::Constant(F::from(1 << 32));
+            vec![q_unpack * (packed - shift * a - b)]
+        });
+
+        Self { bits, q_unpack }
+    }
+}
+
+/// Circuit proving the `num_terms`-th term of the sequence seeded with `a, b`, exposing
+/// `[a * 2^32 + b, F(n)]`.
+#[derive(Clone, Debug)]
+pub struct PackedSeedCircuit {
+    // held as u64 so a test can hand in a seed that doesn't fit in 32 bits
+    a: Value<u64>,
+    b: Value<u64>,
+    num_terms: usize,
+}
+
+impl PackedSeedCircuit {
+    pub fn new(a: u32, b: u32, num_terms: usize) -> Self {
+        Self {
+            a: Value::known(a.into()),
+            b: Value::known(b.into()),
+            num_terms,
+        }
+    }
+
+    /// The sequence rows, the 64-bit decomposition and the unpacking row.
+    pub fn k(&self) -> u32 {
+        k_for_rows(self.num_terms.max(3) - 2 + BITS + 1)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for PackedSeedCircuit {
+    type Config = PackedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        PackedConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.bits.fib;
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.a.map(F::from),
+            self.b.map(F::from),
+            self.num_terms,
+        )?;
+
+        let packed = layouter.assign_region(
+            || "unpack",
+            |mut region| {
+                let value = self.a.zip(self.b).map(|(a, b)| (a << 32).wrapping_add(b));
+                let cells = config.bits.assign_bits(&mut region, value)?;
+                let (_, high) = &cells[BITS / 2 - 1];
+                let (_, packed) = &cells[BITS - 1];
+
+                let offset = BITS;
+                config.q_unpack.enable(&mut region, offset)?;
+                packed.copy_advice(|| "packed", &mut region, fib.elem_1, offset)?;
+                let a = terms[0].copy_advice(|| "a", &mut region, fib.elem_2, offset)?;
+                region.constrain_equal(a.cell(), high.cell())?;
+                terms[1].copy_advice(|| "b", &mut region, fib.elem_3, offset)?;
+                Ok(packed.clone())
+            },
+        )?;
+
+        fib.expose_public(layouter.namespace(|| "packed seeds"), &packed, 0)?;
+        fib.expose_public(layouter.namespace(|| "output"), &terms[terms.len() - 1], 1)
+    }
+}
+
+/// Proves the `num_terms`-th term of the sequence seeded with `a, b`, exposing the seeds
+/// packed into a single public input.
+pub fn prove_packed(a: u32, b: u32, num_terms: usize) -> Result<FibonacciProof, FibError> {
+    let sequence = FibonacciCircuit::<Fp>::new(a.into(), b.into(), num_terms);
+    sequence.validate()?;
+    let output = sequence.output().expect("seeds are known");
+    let circuit = PackedSeedCircuit::new(a, b, num_terms);
+    let public_inputs = vec![Fp::from(pack_seeds(a, b)), output];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_packed`], returning the proven output.
+pub fn verify_packed(proof: &FibonacciProof) -> Result<Fp, FibError> {
+    let circuit = PackedSeedCircuit::new(0, 0, proof.num_terms);
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)?;
+    Ok(proof.public_inputs[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_packed_seeds() {
+        // 3, 7, 10, 17, 27, 44, 71, 115, 186, 301
+        let proof = prove_packed(3, 7, 10).unwrap();
+        assert_eq!(proof.public_inputs[0], Fp::from(3 * (1 << 32) + 7));
+        assert_eq!(verify_packed(&proof).unwrap(), Fp::from(301));
+
+        let mut forged = proof;
+        forged.public_inputs[0] = Fp::from(pack_seeds(7, 3));
+        assert!(matches!(verify_packed(&forged), Err(FibError::Verify)));
+
+        // 3 * 2^32 + (2^32 + 7) is also 4 * 2^32 + 7, but b doesn't fit in its half
+        let circuit = PackedSeedCircuit {
+            b: Value::known((1 << 32) + 7),
+            ..PackedSeedCircuit::new(3, 0, 10)
+        };
+        let output = FibonacciCircuit::<Fp>::new(3, (1 << 32) + 7, 10)
+            .output()
+            .unwrap();
+        let public_inputs = vec![Fp::from(pack_seeds(4, 7)), output];
+        let prover = MockProver::<Fp>::run(circuit.k(), &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,272 @@
+/*
+
+    The sequence padded to a fixed number of rows, so one verifying key covers every term
+    count that fits. Every row runs the recurrence (chained as in the copy-lane layout);
+    the output row is picked by a one-hot witness `sel` instead of by where the copy to
+    the instance sits:
+
+    | elem_3 | index | sel |   acc    | count | index_sum | q_first | q_step
+    -------------------------------------------------------------------------
+    |  F(3)  |   3   |  0  |    0     |   0   |     0     |    1    |   0
+    |  ...   |  ...  | ... |   ...    |  ...  |    ...    |    0    |   1
+    |  F(n)  |   n   |  1  |   F(n)   |   1   |     n     |    0    |   1
+    |  ...   |  ...  |  0  |   F(n)   |   1   |     n     |    0    |   1
+
+    sel * (1 - sel) = 0
+    acc = acc(prev) + sel * elem_3          (acc(prev) = 0 on the first row)
+    count = count(prev) + sel
+    index_sum = index_sum(prev) + sel * index
+
+    On the last row count is pinned to 1, and acc and index_sum are copied to the
+    instance as [F(n), n].
+
+*/
+
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::copy_lane::CopyLaneConfig;
+use crate::error::FibError;
+use crate::fibonacci::FibonacciCircuit;
+
+#[derive(Clone, Debug)]
+pub struct PaddedConfig {
+    pub(crate) lane: CopyLaneConfig,
+    index: Column<Fixed>,
+    sel: Column<Advice>,
+    acc: Column<Advice>,
+    count: Column<Advice>,
+    index_sum: Column<Advice>,
+    q_first: Selector,
+    q_step: Selector,
+}
+
+impl PaddedConfig {
+    pub fn configure<F: Field>(cs: &mut ConstraintSystem<F>) -> Self {
+        let lane = CopyLaneConfig::configure(cs);
+        let index = cs.fixed_column();
+        let sel = cs.advice_column();
+        let acc = cs.advice_column();
+        cs.enable_equality(acc);
+        let count = cs.advice_column();
+        cs.enable_equality(count);
+        let index_sum = cs.advice_column();
+        cs.enable_equality(index_sum);
+        // pins the final count
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let q_first = cs.selector();
+        let q_step = cs.selector();
+
+        // the first row starts the running sums, later rows extend the previous row's
+        for (name, selector, first) in [
+            ("output selection first", q_first, true),
+            ("output selection step", q_step, false),
+        ] {
+            cs.create_gate(name, |virtual_cells| {
+                let q = virtual_cells.query_selector(selector);
+                let elem_3 = virtual_cells.query_advice(lane.elem_3, Rotation::cur());
+                let index = virtual_cells.query_fixed(index, Rotation::cur());
+                let sel = virtual_cells.query_advice(sel, Rotation::cur());
+                let one = Expression::Constant(F::one());
+
+                let mut constraints = vec![q.clone() * sel.clone() * (one - sel.clone())];
+                for (column, term) in [
+                    (acc, sel.clone() * elem_3.clone()),
+                    (count, sel.clone()),
+                    (index_sum, sel.clone() * index.clone()),
+                ] {
+                    let mut sum = virtual_cells.query_advice(column, Rotation::cur()) - term;
+                    if !first {
+                        sum = sum - virtual_cells.query_advice(column, Rotation::prev());
+                    }
+                    constraints.push(q.clone() * sum);
+                }
+                constraints
+            });
+        }
+
+        Self {
+            lane,
+            index,
+            sel,
+            acc,
+            count,
+            index_sum,
+            q_first,
+            q_step,
+        }
+    }
+
+    /// Lays out `rows` padded rows from the seeds, selecting the one at offset
+    /// `output_row` (none if it's past the end, which leaves the count at 0 and fails), and
+    /// returns `[elem_1, elem_2, acc, index_sum]`: the seed cells, then the selected term and
+    /// its index.
+    pub(crate) fn assign_padded<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        elem_1: Value<F>,
+        elem_2: Value<F>,
+        rows: usize,
+        output_row: Value<usize>,
+    ) -> Result<[AssignedCell<F, F>; 4], Error> {
+        layouter.assign_region(
+            || "padded sequence",
+            |mut region| {
+                let [seed_1, seed_2, _] =
+                    self.lane
+                        .assign_rows_with_seeds(&mut region, elem_1, elem_2, rows)?;
+
+                let (mut prev_1, mut prev_2) = (elem_1, elem_2);
+                let mut acc = Value::known(F::zero());
+                let (mut count, mut index_sum) = (Value::known(F::zero()), Value::known(F::zero()));
+                let mut cells = None;
+                for offset in 0..rows {
+                    if offset == 0 {
+                        self.q_first.enable(&mut region, offset)?;
+                    } else {
+                        self.q_step.enable(&mut region, offset)?;
+                    }
+                    let elem_3 = prev_1 + prev_2;
+                    (prev_1, prev_2) = (prev_2, elem_3);
+                    let index = F::from((offset + 3) as u64);
+                    region.assign_fixed(|| "index", self.index, offset, || Value::known(index))?;
+
+                    let selected = output_row.map(|output_row| offset == output_row);
+                    let sel = selected.map(|selected| F::from(selected as u64));
+                    region.assign_advice(|| "sel", self.sel, offset, || sel)?;
+                    acc = selected.zip(acc.zip(elem_3)).map(
+                        |(selected, (acc, elem_3))| {
+                            if selected {
+                                elem_3
+                            } else {
+                                acc
+                            }
+                        },
+                    );
+                    count = count + sel;
+                    index_sum = index_sum + sel * Value::known(index);
+                    cells = Some((
+                        region.assign_advice(|| "acc", self.acc, offset, || acc)?,
+                        region.assign_advice(|| "count", self.count, offset, || count)?,
+                        region.assign_advice(
+                            || "index_sum",
+                            self.index_sum,
+                            offset,
+                            || index_sum,
+                        )?,
+                    ));
+                }
+                let (acc, count, index_sum) = cells.expect("at least one row");
+                region.constrain_constant(count.cell(), F::one())?;
+                Ok([seed_1, seed_2, acc, index_sum])
+            },
+        )
+    }
+}
+
+/// A [`FibonacciCircuit`] padded to `rows` rows, exposing `[F(n), n]`; built by
+/// [`FibonacciCircuit::pad_to_rows`]. Its verifying key depends only on `rows`, not on the
+/// term count. The exposure flags of the inner circuit are ignored.
+#[derive(Clone, Debug)]
+pub struct PaddedCircuit<F: FieldExt> {
+    circuit: FibonacciCircuit<F>,
+    rows: usize,
+}
+
+impl<F: FieldExt> PaddedCircuit<F> {
+    pub(crate) fn new(circuit: FibonacciCircuit<F>, rows: usize) -> Result<Self, FibError> {
+        if circuit.num_terms() < 3 || circuit.rows() > rows {
+            return Err(FibError::BadPublicInputs(format!(
+                "{} terms don't fit in {} padded rows",
+                circuit.num_terms(),
+                rows
+            )));
+        }
+        Ok(Self { circuit, rows })
+    }
+
+    /// `[F(n), n]`, or `None` for a circuit without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        Some(vec![
+            self.circuit.output()?,
+            F::from(self.circuit.num_terms() as u64),
+        ])
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for PaddedCircuit<F> {
+    type Config = PaddedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        PaddedConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (elem_1, elem_2) = self.circuit.seeds();
+        // which row is selected is part of the witness, not of the layout
+        let output_row = Value::known(self.circuit.rows() - 1);
+        let [_, _, acc, index_sum] = config.assign_padded(
+            layouter.namespace(|| "padded sequence"),
+            elem_1,
+            elem_2,
+            self.rows,
+            output_row,
+        )?;
+
+        layouter.constrain_instance(acc.cell(), config.lane.instance, 0)?;
+        layouter.constrain_instance(index_sum.cell(), config.lane.instance, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::{EqAffine, Fp};
+    use halo2_proofs::poly::commitment::Params;
+
+    use super::*;
+
+    #[test]
+    fn test_padded_circuits_share_a_vk() {
+        let k = 5;
+        let params: Params<EqAffine> = Params::new(k);
+        let vk = |num_terms| {
+            let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms)
+                .pad_to_rows(10)
+                .unwrap();
+            let prover =
+                MockProver::run(k, &circuit, vec![circuit.public_inputs().unwrap()]).unwrap();
+            prover.assert_satisfied();
+            let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+            format!("{:?}", vk.pinned())
+        };
+        assert_eq!(vk(5), vk(8));
+
+        // claiming another term count fails
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 8)
+            .pad_to_rows(10)
+            .unwrap();
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(21), Fp::from(9)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        assert!(FibonacciCircuit::<Fp>::new(1, 1, 13)
+            .pad_to_rows(10)
+            .is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Plain parameter struct describing a Fibonacci statement, independent of where the
+//! values come from.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use halo2_proofs::arithmetic::FieldExt;
+
+use crate::fibonacci::{min_k, FibonacciCircuit};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FibParams {
+    pub a: u64,
+    pub b: u64,
+    pub num_terms: usize,
+    /// Circuit size; `None` picks [`min_k`].
+    pub k: Option<u32>,
+}
+
+impl FibParams {
+    pub fn new(a: u64, b: u64, num_terms: usize) -> Self {
+        Self {
+            a,
+            b,
+            num_terms,
+            k: None,
+        }
+    }
+
+    /// The `k` to prove with, resolving `None` to the smallest one that fits.
+    pub fn k(&self) -> u32 {
+        self.k.unwrap_or_else(|| min_k(self.num_terms))
+    }
+
+    /// A hash of the parameters, for keying caches of params or verifying keys. It is only
+    /// stable within one build of the crate, so don't persist it.
+    pub fn statement_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Builds the circuit together with the resolved `k`.
+    pub fn build<F: FieldExt>(&self) -> (FibonacciCircuit<F>, u32) {
+        (
+            FibonacciCircuit::new(self.a, self.b, self.num_terms),
+            self.k(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_k_resolves_to_min_k() {
+        let params = FibParams::new(1, 1, 10);
+        let (circuit, k) = params.build::<Fp>();
+        assert_eq!(k, min_k(10));
+        assert_eq!(k, 4);
+        circuit.verify_mock(k, &[Fp::from(55)]).unwrap();
+
+        let params = FibParams {
+            k: Some(7),
+            ..params
+        };
+        assert_eq!(params.build::<Fp>().1, 7);
+    }
+
+    #[test]
+    fn test_statement_key() {
+        let params = FibParams::new(1, 1, 10);
+        assert_eq!(params, FibParams::new(1, 1, 10));
+        assert_eq!(
+            params.statement_key(),
+            FibParams::new(1, 1, 10).statement_key()
+        );
+
+        for other in [
+            FibParams::new(1, 2, 10),
+            FibParams::new(1, 1, 11),
+            FibParams {
+                k: Some(7),
+                ..params.clone()
+            },
+        ] {
+            assert_ne!(params, other);
+            assert_ne!(params.statement_key(), other.statement_key());
+        }
+    }
+}
@@ -0,0 +1,257 @@
+/*
+
+    Reveals only the parity of F(n). The output is decomposed into 64 bits, most
+    significant first, with a running sum; the last bit is the parity and is the only
+    public input:
+
+    | elem_1 | elem_2 | q_start | q_bits | q_bool
+    ---------------------------------------------
+    |  b_63  |  acc_0 |    1    |   1    |   1
+    |  b_62  |  acc_1 |    0    |   1    |   1
+    |  ...   |  ...   |   ...   |  ...   |  ...
+    |  b_0   | acc_63 |    0    |   0    |   1
+
+    q_start * (acc - bit) = 0
+    q_bits * (acc(next) - 2 * acc - bit(next)) = 0
+    q_bool * bit * (1 - bit) = 0
+
+    Both seeds are pinned to 1. acc_63 is copied from F(n), and b_0 to the instance. 64 bits only fit values below 2^64,
+    so F(n) has to fit in a u64 (n <= 93 for the standard seeds).
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, Region, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::Config;
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::{fib_u64, MAX_U64_INDEX};
+
+pub(crate) const BITS: usize = 64;
+
+#[derive(Clone, Debug)]
+pub struct ParityConfig {
+    pub(crate) fib: Config,
+    pub(crate) q_start: Selector,
+    pub(crate) q_bits: Selector,
+    q_bool: Selector,
+}
+
+impl ParityConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let (bit, acc) = (fib.elem_1, fib.elem_2);
+        let q_start = cs.selector();
+        let q_bits = cs.selector();
+        let q_bool = cs.selector();
+
+        cs.create_gate("decomposition start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(q_start);
+            let bit = virtual_cells.query_advice(bit, Rotation::cur());
+            let acc = virtual_cells.query_advice(acc, Rotation::cur());
+            vec![q_start * (acc - bit)]
+        });
+
+        cs.create_gate("decomposition step", |virtual_cells| {
+            let q_bits = virtual_cells.query_selector(q_bits);
+            let bit_next = virtual_cells.query_advice(bit, Rotation::next());
+            let acc_cur = virtual_cells.query_advice(acc, Rotation::cur());
+            let acc_next = virtual_cells.query_advice(acc, Rotation::next());
+            vec![q_bits * (acc_next - Expression::Constant(F::from(2)) * acc_cur - bit_next)]
+        });
+
+        cs.create_gate("boolean bit", |virtual_cells| {
+            let q_bool = virtual_cells.query_selector(q_bool);
+            let bit = virtual_cells.query_advice(bit, Rotation::cur());
+            vec![q_bool * bit.clone() * (Expression::Constant(F::one()) - bit)]
+        });
+
+        Self {
+            fib,
+            q_start,
+            q_bits,
+            q_bool,
+        }
+    }
+
+    /// Lays out the decomposition of `value` from offset 0 of `region` and returns the
+    /// `(bit, acc)` cells of every row, most significant bit first.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn assign_bits<F: FieldExt>(
+        &self,
+        region: &mut Region<'_, F>,
+        value: Value<u64>,
+    ) -> Result<Vec<(AssignedCell<F, F>, AssignedCell<F, F>)>, Error> {
+        let mut cells = Vec::with_capacity(BITS);
+        for offset in 0..BITS {
+            let shift = BITS - 1 - offset;
+            if offset == 0 {
+                self.q_start.enable(region, offset)?;
+            }
+            if offset + 1 < BITS {
+                self.q_bits.enable(region, offset)?;
+            }
+            self.q_bool.enable(region, offset)?;
+            let bit = value.map(|value| F::from((value >> shift) & 1));
+            // the bits above `shift`, i.e. the running sum so far
+            let acc = value.map(|value| F::from(value.checked_shr(shift as u32).unwrap_or(0)));
+            cells.push((
+                region.assign_advice(|| "bit", self.fib.elem_1, offset, || bit)?,
+                region.assign_advice(|| "acc", self.fib.elem_2, offset, || acc)?,
+            ));
+        }
+        Ok(cells)
+    }
+}
+
+/// Circuit proving the parity of `F(n)` (standard `1, 1` seeds) without revealing `F(n)`.
+#[derive(Clone, Debug)]
+pub struct ParityCircuit {
+    n: usize,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl ParityCircuit {
+    pub fn new(n: usize) -> Result<Self, FibError> {
+        if !(3..=MAX_U64_INDEX).contains(&n) {
+            return Err(FibError::BadPublicInputs(format!(
+                "the parity circuit needs 3 <= n <= {}, got {}",
+                MAX_U64_INDEX, n
+            )));
+        }
+        Ok(Self {
+            n,
+            seed: 1,
+            witness: true,
+        })
+    }
+
+    /// `F(n) mod 2`, the public input.
+    pub fn parity(&self) -> u64 {
+        fib_u64(self.n) & 1
+    }
+
+    fn k(&self) -> u32 {
+        k_for_rows(self.n - 2 + BITS)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ParityCircuit {
+    type Config = ParityConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = ParityConfig::configure(meta);
+        // pins the seeds
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        config
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let witness = |x: u64| {
+            if self.witness {
+                Value::known(x)
+            } else {
+                Value::unknown()
+            }
+        };
+        let seed = witness(self.seed).map(F::from);
+        let terms = fib.assign_terms(layouter.namespace(|| "sequence"), seed, seed, self.n)?;
+        let output = &terms[self.n - 1];
+
+        let value = witness(self.seed * fib_u64(self.n));
+        let parity = layouter.assign_region(
+            || "bit decomposition",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::one())?;
+
+                let cells = config.assign_bits(&mut region, value)?;
+                let (bit, acc) = cells.last().expect("at least one bit");
+                region.constrain_equal(acc.cell(), output.cell())?;
+                Ok(bit.clone())
+            },
+        )?;
+
+        fib.expose_public(layouter, &parity, 0)
+    }
+}
+
+/// Proves the parity of `F(n)`, keeping `F(n)` itself private.
+pub fn prove_parity(n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = ParityCircuit::new(n)?;
+    let public_inputs = vec![Fp::from(circuit.parity())];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_parity`].
+pub fn verify_parity(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = ParityCircuit::new(proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_reveals_only_parity() {
+        // F(10) = 55
+        let proof = prove_parity(10).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::one()]);
+        assert!(!proof.public_inputs.contains(&Fp::from(55)));
+        verify_parity(&proof).unwrap();
+
+        let mut flipped = proof;
+        flipped.public_inputs = vec![Fp::zero()];
+        assert!(matches!(verify_parity(&flipped), Err(FibError::Verify)));
+
+        // F(9) = 34, and the largest u64 term
+        for n in [9, MAX_U64_INDEX] {
+            let circuit = ParityCircuit::new(n).unwrap();
+            let instance = vec![Fp::from(circuit.parity())];
+            let prover = MockProver::run(circuit.k(), &circuit, vec![instance]).unwrap();
+            prover.assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 2 make F(10) = 110, which is even
+        let circuit = ParityCircuit {
+            seed: 2,
+            ..ParityCircuit::new(10).unwrap()
+        };
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::zero()]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
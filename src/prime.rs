@@ -0,0 +1,184 @@
+//! `F(p) mod p` for a public prime `p`, which by the law of quadratic reciprocity is the
+//! Legendre symbol `(p / 5)`: `1` if `p = ±1 mod 5`, `p - 1` if `p = ±2 mod 5`, and `0`
+//! for `p = 5`.
+//!
+//! The sequence `1, 1, ...` is laid out mod `p` with the [`ModularConfig`] gadget up to
+//! index `p`, with both seeds pinned to 1, and only the last residue is exposed. The index is public through the fixed
+//! modulus column, so the verifying key pins `p`.
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+
+use crate::error::FibError;
+use crate::modular::{ModularCircuit, ModularConfig};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::fib_mod;
+
+/// Circuit proving `F(p) mod p` for the standard `1, 1` seeds.
+#[derive(Clone, Debug)]
+pub struct PrimePropertyCircuit {
+    p: u64,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl PrimePropertyCircuit {
+    pub fn new(p: u64) -> Result<Self, FibError> {
+        if !(3..=ModularCircuit::MAX_MODULUS).contains(&p) || !is_prime(p) {
+            return Err(FibError::BadPublicInputs(format!(
+                "the index must be an odd prime up to {}, got {}",
+                ModularCircuit::MAX_MODULUS,
+                p
+            )));
+        }
+        Ok(Self {
+            p,
+            seed: 1,
+            witness: true,
+        })
+    }
+
+    /// `F(p) mod p`, the public input.
+    pub fn residue(&self) -> u64 {
+        fib_mod(1, 1, self.p as usize, self.p)[self.p as usize - 1]
+    }
+
+    /// The residue the congruence predicts, `(p / 5)` reduced mod `p`.
+    pub fn expected_residue(&self) -> u64 {
+        match self.p % 5 {
+            0 => 0,
+            1 | 4 => 1,
+            _ => self.p - 1,
+        }
+    }
+
+    fn k(&self) -> u32 {
+        // the table (one row per residue plus the (0, 0) row) outgrows the sequence
+        k_for_rows(self.p as usize + 2)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for PrimePropertyCircuit {
+    type Config = ModularConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ModularConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.load_table(layouter.namespace(|| "table"), &[self.p])?;
+
+        let num_terms = self.p as usize;
+        let residues = fib_mod(self.seed, self.seed, num_terms, self.p);
+        let residues = if self.witness {
+            Value::known(residues.as_slice())
+        } else {
+            Value::unknown()
+        };
+        let cells = config.assign_standard_sequence(
+            layouter.namespace(|| "sequence"),
+            residues,
+            num_terms,
+            self.p,
+        )?;
+
+        config.expose_public(layouter, &cells[num_terms - 1], 0)
+    }
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// Proves `F(p) mod p` for the prime `p`, exposing the residue.
+pub fn prove_fib_prime_property(p: u64) -> Result<FibonacciProof, FibError> {
+    let circuit = PrimePropertyCircuit::new(p)?;
+    let public_inputs = vec![Fp::from(circuit.residue())];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: p as usize,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_fib_prime_property`] and checks that the proven
+/// residue is the one the congruence predicts.
+pub fn verify_fib_prime_property(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = PrimePropertyCircuit::new(proof.num_terms as u64)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)?;
+    if proof.public_inputs != [Fp::from(circuit.expected_residue())] {
+        return Err(FibError::BadPublicInputs(format!(
+            "F({0}) mod {0} is not the Legendre symbol ({0} / 5)",
+            circuit.p
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_small_primes() {
+        // F(7) = 13 = -1 mod 7, F(11) = 89 = 1 mod 11, F(5) = 5 = 0 mod 5
+        for (p, residue) in [(7, 6), (11, 1), (5, 0)] {
+            let circuit = PrimePropertyCircuit::new(p).unwrap();
+            assert_eq!(circuit.residue(), residue);
+            assert_eq!(circuit.expected_residue(), residue);
+        }
+
+        let proof = prove_fib_prime_property(7).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(6)]);
+        verify_fib_prime_property(&proof).unwrap();
+
+        let mut forged = proof;
+        forged.public_inputs = vec![Fp::from(1)];
+        assert!(matches!(
+            verify_fib_prime_property(&forged),
+            Err(FibError::Verify)
+        ));
+
+        for p in [2, 9, ModularCircuit::MAX_MODULUS + 1] {
+            assert!(matches!(
+                PrimePropertyCircuit::new(p),
+                Err(FibError::BadPublicInputs(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        // seeds 2, 2 give 2 * F(7) = 26 = 5 mod 7
+        let circuit = PrimePropertyCircuit {
+            seed: 2,
+            ..PrimePropertyCircuit::new(7).unwrap()
+        };
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(5)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
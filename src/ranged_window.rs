@@ -0,0 +1,312 @@
+/*
+
+    A window of the standard sequence whose start index stays private and is only shown to
+    lie in a public range [lo, hi]:
+
+    | instance
+    ----------
+    | F(last)
+    |   lo
+    |   hi
+
+    The sequence is laid out from the seeds 1, 1 (pinned) up to index hi + num_terms - 1,
+    padded as in the padded layout, and the one-hot selection picks the private last term of
+    the window and its index, last = base + num_terms - 1. One row of the plain chip, with
+    num_terms - 1 pinned in elem_2, recovers base from it:
+
+    | elem_1 |     elem_2    | elem_3 | q_fib
+    -----------------------------------------
+    |  base  | num_terms - 1 |  last  |   1
+
+    The differences d_lo = base - lo and d_hi = hi - base are laid out as rows of the plain
+    chip, whose gate gives lo + d_lo = base and base + d_hi = hi, and each difference is
+    decomposed into 64 bits as in the parity layout. Both being below 2^64 puts base in
+    [lo, hi], since the field is far larger than 2^65. So F(last) really is the term at
+    index base + num_terms - 1 for some base in the range. The instance column of the bit
+    chip is left empty.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+
+use crate::error::FibError;
+use crate::fibonacci::{known, max_terms_for_k, MAX_SUPPORTED_K};
+use crate::padded::PaddedConfig;
+use crate::parity::{ParityConfig, BITS};
+use crate::proof::k_for_rows;
+use crate::reference::fib_mod_p;
+use crate::window::check_indices;
+
+#[derive(Clone, Debug)]
+pub struct RangedWindowConfig {
+    padded: PaddedConfig,
+    bits: ParityConfig,
+}
+
+impl RangedWindowConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let padded = PaddedConfig::configure(cs);
+        // the seeds get pinned
+        cs.enable_equality(padded.lane.elem_1);
+        cs.enable_equality(padded.lane.elem_2);
+        Self {
+            padded,
+            bits: ParityConfig::configure(cs),
+        }
+    }
+
+    /// Lays out `base + (num_terms - 1) = last`, with `last` copied from `last` and
+    /// `num_terms - 1` pinned, and returns the `base` cell. Also pins both `seeds` to 1.
+    fn assign_base<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        seeds: [&AssignedCell<F, F>; 2],
+        last: &AssignedCell<F, F>,
+        num_terms: usize,
+        base: Value<u64>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let fib = &self.bits.fib;
+        layouter.assign_region(
+            || "window start",
+            |mut region| {
+                for seed in seeds {
+                    region.constrain_constant(seed.cell(), F::one())?;
+                }
+
+                let offset = 0;
+                fib.q_fib.enable(&mut region, offset)?;
+                let base =
+                    region.assign_advice(|| "base", fib.elem_1, offset, || base.map(F::from))?;
+                let length = F::from(num_terms as u64 - 1);
+                let shift = region.assign_advice(
+                    || "num_terms - 1",
+                    fib.elem_2,
+                    offset,
+                    || Value::known(length),
+                )?;
+                region.constrain_constant(shift.cell(), length)?;
+                last.copy_advice(|| "last", &mut region, fib.elem_3, offset)?;
+                Ok(base)
+            },
+        )
+    }
+
+    /// Lays out `elem_1 + difference = elem_3` with the difference decomposed into bits,
+    /// where `elem_1` and `elem_3` are either copied from `cell` or read from the instance
+    /// row `row`, as `cell_first` picks.
+    fn assign_difference<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        row: usize,
+        cell_first: bool,
+        difference: Value<u64>,
+    ) -> Result<(), Error> {
+        let fib = &self.bits.fib;
+        let instance = self.padded.lane.instance;
+        layouter.assign_region(
+            || "index bound",
+            |mut region| {
+                let cells = self.bits.assign_bits(&mut region, difference)?;
+                let (_, acc) = cells.last().expect("at least one bit");
+
+                fib.q_fib.enable(&mut region, BITS)?;
+                let (copied, public) = if cell_first {
+                    (fib.elem_1, fib.elem_3)
+                } else {
+                    (fib.elem_3, fib.elem_1)
+                };
+                cell.copy_advice(|| "base", &mut region, copied, BITS)?;
+                region.assign_advice_from_instance(|| "bound", instance, row, public, BITS)?;
+                acc.copy_advice(|| "difference", &mut region, fib.elem_2, BITS)?;
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Circuit exposing `[F(last), lo, hi]` for the window of `num_terms` terms of the
+/// standard `1, 1` sequence starting at a private index `base`, proving
+/// `lo <= base <= hi` without revealing `base`.
+#[derive(Clone, Debug)]
+pub struct RangedWindowCircuit<F: FieldExt> {
+    base: Value<u64>,
+    lo: u64,
+    hi: u64,
+    num_terms: usize,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: FieldExt> RangedWindowCircuit<F> {
+    /// A window starting at index `base`, claimed to lie within `[lo, hi]`. An out-of-range
+    /// `base` is accepted here and fails verification.
+    pub fn new(base: u64, num_terms: usize, lo: u64, hi: u64) -> Result<Self, FibError> {
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        if lo == 0 || lo > hi {
+            return Err(FibError::BadPublicInputs(format!(
+                "[{}, {}] is not a range of indices",
+                lo, hi
+            )));
+        }
+        check_indices(base, num_terms)?;
+        check_indices(hi, num_terms)?;
+        // every term up to the last one of a window starting at hi is laid out
+        let laid_out = hi as usize + num_terms - 1;
+        if laid_out > max_terms_for_k(MAX_SUPPORTED_K) {
+            return Err(FibError::TooManyTerms(laid_out));
+        }
+        Ok(Self {
+            base: Value::known(base),
+            lo,
+            hi,
+            num_terms,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    /// The public inputs `[F(last), lo, hi]`, or `None` without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let last = known(self.base)? as usize + self.num_terms - 1;
+        Some(vec![
+            fib_mod_p::<F>(1, 1, last),
+            F::from(self.lo),
+            F::from(self.hi),
+        ])
+    }
+
+    /// The padded rows, one per term from `F(3)` to `F(hi + num_terms - 1)`.
+    fn rows(&self) -> usize {
+        self.hi as usize + self.num_terms - 3
+    }
+
+    /// The smallest `k` fitting the padded sequence, the start row and both bound checks.
+    pub fn k(&self) -> u32 {
+        k_for_rows(self.rows().max(2 * (BITS + 1) + 1))
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for RangedWindowCircuit<F> {
+    type Config = RangedWindowConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            base: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        RangedWindowConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let seed = self.base.map(|_| F::one());
+        // the offset of F(last) is last - 3; a base too small for that selects nothing
+        let output_row = self.base.map(|base| {
+            (base as usize + self.num_terms)
+                .checked_sub(4)
+                .unwrap_or(usize::MAX)
+        });
+        let [seed_1, seed_2, output, last] = config.padded.assign_padded(
+            layouter.namespace(|| "sequence"),
+            seed,
+            seed,
+            self.rows(),
+            output_row,
+        )?;
+        layouter.constrain_instance(output.cell(), config.padded.lane.instance, 0)?;
+
+        let base = config.assign_base(
+            layouter.namespace(|| "window start"),
+            [&seed_1, &seed_2],
+            &last,
+            self.num_terms,
+            self.base,
+        )?;
+
+        // out-of-range differences wrap around, and the gate rejects them
+        let (lo, hi) = (self.lo, self.hi);
+        config.assign_difference(
+            layouter.namespace(|| "lower bound"),
+            &base,
+            1,
+            false,
+            self.base.map(|base| base.wrapping_sub(lo)),
+        )?;
+        config.assign_difference(
+            layouter.namespace(|| "upper bound"),
+            &base,
+            2,
+            true,
+            self.base.map(|base| hi.wrapping_sub(base)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_start_index_in_range() {
+        // F(5) = 5, F(6) = 8, ..., F(14) = 377
+        for (lo, hi, in_range) in [(3, 10, true), (5, 5, true), (6, 10, false), (1, 4, false)] {
+            let circuit = RangedWindowCircuit::<Fp>::new(5, 10, lo, hi).unwrap();
+            let public_inputs = circuit.public_inputs().unwrap();
+            assert_eq!(
+                public_inputs,
+                vec![Fp::from(377), Fp::from(lo), Fp::from(hi)]
+            );
+            let instances = vec![public_inputs, vec![]];
+            let prover = MockProver::run(circuit.k(), &circuit, instances).unwrap();
+            assert_eq!(prover.verify().is_ok(), in_range);
+        }
+    }
+
+    #[test]
+    fn test_output_bound_to_the_sequence() {
+        // 7 is no term of the sequence, so no base in range yields it
+        let circuit = RangedWindowCircuit::<Fp>::new(5, 10, 3, 10).unwrap();
+        let instances = vec![vec![Fp::from(7), Fp::from(3), Fp::from(10)], vec![]];
+        let prover = MockProver::run(circuit.k(), &circuit, instances).unwrap();
+        assert!(prover.verify().is_err());
+
+        // F(16) = 987 is a term, but only for base 7, out of [3, 6]
+        let circuit = RangedWindowCircuit::<Fp>::new(7, 10, 3, 6).unwrap();
+        let instances = vec![vec![Fp::from(987), Fp::from(3), Fp::from(6)], vec![]];
+        let prover = MockProver::run(circuit.k(), &circuit, instances).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_indices_out_of_range_rejected() {
+        assert!(matches!(
+            RangedWindowCircuit::<Fp>::new(u64::MAX - 2, 10, 1, 10),
+            Err(FibError::BadPublicInputs(_))
+        ));
+        assert!(matches!(
+            RangedWindowCircuit::<Fp>::new(5, 10, 1, u64::MAX - 2),
+            Err(FibError::BadPublicInputs(_))
+        ));
+        assert!(matches!(
+            RangedWindowCircuit::<Fp>::new(5, 10, 1, 1 << 40),
+            Err(FibError::TooManyTerms(_))
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+/*
+
+    Proves F(n + 1) / F(n) is within a public tolerance of the golden ratio. A quotient of
+    field elements has no size to compare, so the ratio is witnessed in fixed point, as
+    r = floor(F(n + 1) * 2^32 / F(n)) with the remainder of the division, and compared with
+    PHI = floor(phi * 2^32):
+
+    | elem_1 |  elem_2 | elem_3 | q_div | q_lo | q_hi | q_rem
+    ---------------------------------------------------------
+    |    r   |   F(n)  |  rem   |   1   |  0   |  0   |   0
+    | F(n+1) |         |        |   0   |  0   |  0   |   0
+    |    r   |    t    |  d_lo  |   0   |  1   |  0   |   0
+    |    r   |    t    |  d_hi  |   0   |  0   |  1   |   0
+    |  F(n)  |   rem   |  d_rem |   0   |  0   |  0   |   1
+
+    q_div * (r * F(n) + rem - 2^32 * F(n+1)(next)) = 0
+    q_lo * (r + t - PHI - d_lo) = 0
+    q_hi * (PHI + t - r - d_hi) = 0
+    q_rem * (F(n) - rem - 1 - d_rem) = 0
+
+    r, rem, d_lo, d_hi and d_rem are each decomposed into 64 bits as in the parity layout,
+    so they are small and non-negative: rem < F(n), so r is the fixed-point quotient, and
+    PHI - t <= r <= PHI + t. Both r and PHI are rounded down, so the real ratio is within
+    (t + 1) / 2^32 of phi. The terms have to fit in a u64 for the products not to wrap.
+
+    The seeds and t are the public inputs.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::parity::{ParityConfig, BITS};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::{fib_u64, MAX_U64_INDEX};
+
+/// Fractional bits of the fixed-point ratio; the tolerance is in units of `2^-RATIO_BITS`.
+pub const RATIO_BITS: u32 = 32;
+
+/// `floor(phi * 2^RATIO_BITS)`.
+pub const PHI_FIXED: u64 = 6_949_403_065;
+
+/// `floor(F(n + 1) * 2^RATIO_BITS / F(n))` and the remainder of the division.
+pub fn ratio_fixed(n: usize) -> (u64, u64) {
+    let (num, den) = ((fib_u64(n + 1) as u128) << RATIO_BITS, fib_u64(n) as u128);
+    ((num / den) as u64, (num % den) as u64)
+}
+
+#[derive(Clone, Debug)]
+pub struct RatioConfig {
+    bits: ParityConfig,
+    q_div: Selector,
+    q_lo: Selector,
+    q_hi: Selector,
+    q_rem: Selector,
+}
+
+impl RatioConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let bits = ParityConfig::configure(cs);
+        let fib = bits.fib;
+        let q_div = cs.selector();
+        let q_lo = cs.selector();
+        let q_hi = cs.selector();
+        let q_rem = cs.selector();
+        let phi = Expression::Constant(F::from(PHI_FIXED));
+
+        cs.create_gate("fixed-point division", |virtual_cells| {
+            let q_div = virtual_cells.query_selector(q_div);
+            let r = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let den = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let rem = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let num = virtual_cells.query_advice(fib.elem_1, Rotation::next());
+            let scale = Expression::Constant(F::from(1 << RATIO_BITS));
+            vec![q_div * (r * den + rem - scale * num)]
+        });
+
+        cs.create_gate("ratio lower bound", |virtual_cells| {
+            let q_lo = virtual_cells.query_selector(q_lo);
+            let r = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let t = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let d = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_lo * (r + t - phi.clone() - d)]
+        });
+
+        cs.create_gate("ratio upper bound", |virtual_cells| {
+            let q_hi = virtual_cells.query_selector(q_hi);
+            let r = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let t = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let d = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_hi * (phi.clone() + t - r - d)]
+        });
+
+        cs.create_gate("remainder bound", |virtual_cells| {
+            let q_rem = virtual_cells.query_selector(q_rem);
+            let den = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let rem = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let d = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_rem * (den - rem - Expression::Constant(F::one()) - d)]
+        });
+
+        Self {
+            bits,
+            q_div,
+            q_lo,
+            q_hi,
+            q_rem,
+        }
+    }
+
+    /// Decomposes `value` into 64 bits in its own region, tying the result to `cell`.
+    fn range_check<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        value: Value<u64>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                let cells = self.bits.assign_bits(&mut region, value)?;
+                let (_, acc) = cells.last().expect("at least one bit");
+                region.constrain_equal(acc.cell(), cell.cell())
+            },
+        )
+    }
+}
+
+/// Circuit proving `|F(n + 1) / F(n) - phi| <= (tolerance + 1) / 2^32` for the standard
+/// `1, 1` seeds. The public inputs are `[1, 1, tolerance]`.
+#[derive(Clone, Debug)]
+pub struct RatioCircuit {
+    n: usize,
+    tolerance: u64,
+    witness: bool,
+}
+
+impl RatioCircuit {
+    pub fn new(n: usize, tolerance: u64) -> Result<Self, FibError> {
+        if !(3..MAX_U64_INDEX).contains(&n) {
+            return Err(FibError::BadPublicInputs(format!(
+                "the ratio circuit needs 3 <= n < {}, got {}",
+                MAX_U64_INDEX, n
+            )));
+        }
+        Ok(Self {
+            n,
+            tolerance,
+            witness: true,
+        })
+    }
+
+    /// The sequence rows, the ratio rows and five 64-bit decompositions.
+    fn k(&self) -> u32 {
+        k_for_rows(self.n - 1 + 5 + 5 * BITS)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for RatioCircuit {
+    type Config = RatioConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        RatioConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.bits.fib;
+        let witness = |value: u64| {
+            if self.witness {
+                Value::known(value)
+            } else {
+                Value::unknown()
+            }
+        };
+        let seed = witness(1).map(F::from);
+        let terms = fib.assign_terms(layouter.namespace(|| "sequence"), seed, seed, self.n + 1)?;
+        let (den, num) = (&terms[self.n - 1], &terms[self.n]);
+
+        // wrapping, so an out-of-tolerance witness still lays out and fails the checks
+        let (r, rem) = ratio_fixed(self.n);
+        let t = self.tolerance;
+        let d_lo = witness(r.wrapping_add(t).wrapping_sub(PHI_FIXED));
+        let d_hi = witness(PHI_FIXED.wrapping_add(t).wrapping_sub(r));
+        let d_rem = witness(fib_u64(self.n) - rem - 1);
+        let (r, rem, t) = (witness(r), witness(rem), witness(t));
+        let field = |value: Value<u64>| value.map(F::from);
+
+        let cells = layouter.assign_region(
+            || "ratio",
+            |mut region| {
+                config.q_div.enable(&mut region, 0)?;
+                let r_cell = region.assign_advice(|| "r", fib.elem_1, 0, || field(r))?;
+                den.copy_advice(|| "F(n)", &mut region, fib.elem_2, 0)?;
+                let rem_cell = region.assign_advice(|| "rem", fib.elem_3, 0, || field(rem))?;
+                num.copy_advice(|| "F(n + 1)", &mut region, fib.elem_1, 1)?;
+
+                config.q_lo.enable(&mut region, 2)?;
+                r_cell.copy_advice(|| "r", &mut region, fib.elem_1, 2)?;
+                let t_cell = region.assign_advice(|| "t", fib.elem_2, 2, || field(t))?;
+                let d_lo_cell = region.assign_advice(|| "d_lo", fib.elem_3, 2, || field(d_lo))?;
+
+                config.q_hi.enable(&mut region, 3)?;
+                r_cell.copy_advice(|| "r", &mut region, fib.elem_1, 3)?;
+                t_cell.copy_advice(|| "t", &mut region, fib.elem_2, 3)?;
+                let d_hi_cell = region.assign_advice(|| "d_hi", fib.elem_3, 3, || field(d_hi))?;
+
+                config.q_rem.enable(&mut region, 4)?;
+                den.copy_advice(|| "F(n)", &mut region, fib.elem_1, 4)?;
+                rem_cell.copy_advice(|| "rem", &mut region, fib.elem_2, 4)?;
+                let d_rem_cell =
+                    region.assign_advice(|| "d_rem", fib.elem_3, 4, || field(d_rem))?;
+
+                Ok([
+                    (r_cell, r),
+                    (rem_cell, rem),
+                    (d_lo_cell, d_lo),
+                    (d_hi_cell, d_hi),
+                    (d_rem_cell, d_rem),
+                    (t_cell, t),
+                ])
+            },
+        )?;
+        let [checked @ .., (t_cell, _)] = cells;
+
+        for (cell, value) in &checked {
+            config.range_check(layouter.namespace(|| "ratio value"), cell, *value)?;
+        }
+
+        fib.expose_public(layouter.namespace(|| "seed a"), &terms[0], 0)?;
+        fib.expose_public(layouter.namespace(|| "seed b"), &terms[1], 1)?;
+        fib.expose_public(layouter.namespace(|| "tolerance"), &t_cell, 2)
+    }
+}
+
+/// Proves `F(n + 1) / F(n)` is within `(tolerance + 1) / 2^32` of the golden ratio, with
+/// `tolerance` in units of `2^-RATIO_BITS`.
+pub fn prove_ratio_bound(n: usize, tolerance: u64) -> Result<FibonacciProof, FibError> {
+    let circuit = RatioCircuit::new(n, tolerance)?;
+    let (r, _) = ratio_fixed(n);
+    if r.abs_diff(PHI_FIXED) > tolerance {
+        return Err(FibError::BadPublicInputs(format!(
+            "F({}) / F({}) is {} / 2^32 away from phi, more than the tolerance {}",
+            n + 1,
+            n,
+            r.abs_diff(PHI_FIXED),
+            tolerance
+        )));
+    }
+    let public_inputs = vec![Fp::one(), Fp::one(), Fp::from(tolerance)];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_ratio_bound`]; the tolerance is its last public
+/// input.
+pub fn verify_ratio_bound(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = RatioCircuit::new(proof.num_terms, 0)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_ratio_approaches_phi() {
+        // F(41) / F(40) = 165580141 / 102334155 is within 10^-16 of phi, so even the
+        // fixed-point values agree
+        let proof = prove_ratio_bound(40, 0).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::one(), Fp::one(), Fp::zero()]);
+        verify_ratio_bound(&proof).unwrap();
+
+        let mut looser = proof;
+        looser.public_inputs[2] = Fp::from(5);
+        assert!(matches!(verify_ratio_bound(&looser), Err(FibError::Verify)));
+
+        // F(11) / F(10) = 89 / 55 is off by about 1.5 * 10^-4, or 634923 / 2^32
+        assert_eq!(ratio_fixed(10).0 - PHI_FIXED, 634_923);
+        assert!(matches!(
+            prove_ratio_bound(10, 634_922),
+            Err(FibError::BadPublicInputs(_))
+        ));
+        for (tolerance, satisfied) in [(634_922, false), (634_923, true)] {
+            let circuit = RatioCircuit::new(10, tolerance).unwrap();
+            let instance = vec![Fp::one(), Fp::one(), Fp::from(tolerance)];
+            let prover = MockProver::<Fp>::run(circuit.k(), &circuit, vec![instance]).unwrap();
+            assert_eq!(prover.verify().is_ok(), satisfied);
+        }
+    }
+}
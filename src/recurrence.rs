@@ -0,0 +1,237 @@
+/*
+
+    x_n = coef_1 * x_{n-1} + coef_2 * x_{n-2}, with the coefficients as public inputs
+
+    Fibonacci, Lucas: coef_1 = 1, coef_2 = 1 (Lucas starts from 2, 1)
+    Pell:             coef_1 = 2, coef_2 = 1
+
+    | elem_1 | elem_2 | elem_3 | coef_1 | coef_2 | q_rec | instance
+    ----------------------------------------------------------------
+    |    0   |    1   |    2   |    2   |    1   |   1   | coef_1
+    |    1   |    2   |    5   |    2   |    1   |   1   | coef_2
+    |    2   |    5   |   12   |    2   |    1   |   1   | output
+
+    q_rec * (coef_1 * elem_2 + coef_2 * elem_1 - elem_3) = 0
+
+    The coefficients are copied from the instance column into the first row and then
+    down every row, so one verifying key covers every choice of coefficients.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::fibonacci::Config;
+
+#[derive(Clone, Debug)]
+pub struct RecurrenceConfig {
+    fib: Config,
+    coef_1: Column<Advice>,
+    coef_2: Column<Advice>,
+    q_rec: Selector,
+}
+
+impl RecurrenceConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let coef_1 = cs.advice_column();
+        cs.enable_equality(coef_1);
+        let coef_2 = cs.advice_column();
+        cs.enable_equality(coef_2);
+        let q_rec = cs.selector();
+
+        cs.create_gate("linear recurrence", |virtual_cells| {
+            let q_rec = virtual_cells.query_selector(q_rec);
+            let elem_1 = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let elem_2 = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let elem_3 = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let coef_1 = virtual_cells.query_advice(coef_1, Rotation::cur());
+            let coef_2 = virtual_cells.query_advice(coef_2, Rotation::cur());
+
+            vec![q_rec * (coef_1 * elem_2 + coef_2 * elem_1 - elem_3)]
+        });
+
+        Self {
+            fib,
+            coef_1,
+            coef_2,
+            q_rec,
+        }
+    }
+}
+
+/// Circuit proving the `num_terms`-th term of a second-order linear recurrence whose
+/// coefficients are public. Public inputs: `[coef_1, coef_2, output]`.
+#[derive(Clone, Debug)]
+pub struct RecurrenceCircuit<F: FieldExt> {
+    seeds: Value<(F, F)>,
+    coefficients: (u64, u64),
+    num_terms: usize,
+}
+
+impl<F: FieldExt> RecurrenceCircuit<F> {
+    pub fn new(seeds: (u64, u64), coefficients: (u64, u64), num_terms: usize) -> Self {
+        Self {
+            seeds: Value::known((F::from(seeds.0), F::from(seeds.1))),
+            coefficients,
+            num_terms,
+        }
+    }
+
+    pub fn fibonacci(num_terms: usize) -> Self {
+        Self::new((1, 1), (1, 1), num_terms)
+    }
+
+    pub fn lucas(num_terms: usize) -> Self {
+        Self::new((2, 1), (1, 1), num_terms)
+    }
+
+    pub fn pell(num_terms: usize) -> Self {
+        Self::new((0, 1), (2, 1), num_terms)
+    }
+
+    fn step(&self, a: F, b: F) -> F {
+        F::from(self.coefficients.0) * b + F::from(self.coefficients.1) * a
+    }
+
+    /// `[coef_1, coef_2, output]`, or `None` without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let (mut a, mut b) = crate::fibonacci::known(self.seeds)?;
+        for _ in 2..self.num_terms {
+            (a, b) = (b, self.step(a, b));
+        }
+        Some(vec![
+            F::from(self.coefficients.0),
+            F::from(self.coefficients.1),
+            b,
+        ])
+    }
+
+    /// Rows used by the layout.
+    pub fn rows(&self) -> usize {
+        self.num_terms.saturating_sub(2)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for RecurrenceCircuit<F> {
+    type Config = RecurrenceConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            seeds: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        RecurrenceConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let output = layouter.assign_region(
+            || "linear recurrence",
+            |mut region| {
+                let fib = &config.fib;
+                let (seed_1, seed_2) = self.seeds.unzip();
+                let mut elem_1 = region.assign_advice(|| "elem_1", fib.elem_1, 0, || seed_1)?;
+                let mut elem_2 = region.assign_advice(|| "elem_2", fib.elem_2, 0, || seed_2)?;
+                let mut coef_1 = region.assign_advice_from_instance(
+                    || "coef_1",
+                    fib.instance,
+                    0,
+                    config.coef_1,
+                    0,
+                )?;
+                let mut coef_2 = region.assign_advice_from_instance(
+                    || "coef_2",
+                    fib.instance,
+                    1,
+                    config.coef_2,
+                    0,
+                )?;
+
+                for offset in 0..self.rows() {
+                    config.q_rec.enable(&mut region, offset)?;
+                    if offset > 0 {
+                        coef_1 =
+                            coef_1.copy_advice(|| "coef_1", &mut region, config.coef_1, offset)?;
+                        coef_2 =
+                            coef_2.copy_advice(|| "coef_2", &mut region, config.coef_2, offset)?;
+                    }
+                    let elem_3 = elem_1
+                        .value()
+                        .zip(elem_2.value())
+                        .map(|(&a, &b)| self.step(a, b));
+                    let elem_3 =
+                        region.assign_advice(|| "elem_3", fib.elem_3, offset, || elem_3)?;
+
+                    if offset + 1 < self.rows() {
+                        elem_1 = elem_2.copy_advice(
+                            || "copy elem_2 into next elem_1",
+                            &mut region,
+                            fib.elem_1,
+                            offset + 1,
+                        )?;
+                        elem_2 = elem_3.copy_advice(
+                            || "copy elem_3 into next elem_2",
+                            &mut region,
+                            fib.elem_2,
+                            offset + 1,
+                        )?;
+                    } else {
+                        return Ok(elem_3);
+                    }
+                }
+                Err(Error::Synthesis)
+            },
+        )?;
+
+        config.fib.expose_public(layouter, &output, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::{EqAffine, Fp};
+    use halo2_proofs::poly::commitment::Params;
+
+    use super::*;
+    use crate::proof::{create_circuit_proof, verify_with_vk};
+
+    #[test]
+    fn test_coefficients_switch_under_one_vk() {
+        let num_terms = 10;
+        let fibonacci = RecurrenceCircuit::<Fp>::fibonacci(num_terms);
+        let lucas = RecurrenceCircuit::<Fp>::lucas(num_terms);
+        let pell = RecurrenceCircuit::<Fp>::pell(num_terms);
+
+        let k = 5;
+        let params: Params<EqAffine> = Params::new(k);
+        let vk = keygen_vk(&params, &fibonacci.without_witnesses()).unwrap();
+
+        for (circuit, output) in [(fibonacci, 55), (lucas, 76), (pell, 985)] {
+            let public_inputs = circuit.public_inputs().unwrap();
+            assert_eq!(public_inputs[2], Fp::from(output));
+
+            let proof = create_circuit_proof(&circuit, &public_inputs, k).unwrap();
+            verify_with_vk(&params, &vk, &public_inputs, &proof).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_wrong_coefficients_fail() {
+        let pell = RecurrenceCircuit::<Fp>::pell(10);
+        let mut public_inputs = pell.public_inputs().unwrap();
+        public_inputs[0] = Fp::one();
+        let prover = halo2_proofs::dev::MockProver::run(5, &pell, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
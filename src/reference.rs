@@ -0,0 +1,101 @@
+//! Plain-Rust reference values for the sequences proven by the circuits.
+
+use halo2_proofs::arithmetic::Field;
+
+/// The largest index whose standard Fibonacci term fits in a `u64`.
+pub const MAX_U64_INDEX: usize = 93;
+
+/// The `n`-th standard Fibonacci number (`F(1) = F(2) = 1`), for `n <= MAX_U64_INDEX`.
+pub fn fib_u64(n: usize) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    // stops at `(F(n - 1), F(n))`, so `F(n + 1)` is never computed and can't overflow
+    let (mut a, mut b) = (0u64, 1u64);
+    for _ in 1..n {
+        (a, b) = (b, a + b);
+    }
+    b
+}
+
+/// The first `num_terms` terms of the sequence seeded with `a, b`, reduced mod `modulus`.
+pub fn fib_mod(a: u64, b: u64, num_terms: usize, modulus: u64) -> Vec<u64> {
+    let mut terms = vec![a % modulus, b % modulus];
+    while terms.len() < num_terms {
+        let next = (terms[terms.len() - 2] + terms[terms.len() - 1]) % modulus;
+        terms.push(next);
+    }
+    terms.truncate(num_terms);
+    terms
+}
+
+/// A field the reference sequence can be computed in: anything with a `u64` embedding.
+pub trait FibField: Field + From<u64> {}
+
+impl<F: Field + From<u64>> FibField for F {}
+
+/// The `num_terms`-th term of the sequence seeded with `a, b`, computed in `F`, i.e. reduced
+/// mod the field's modulus.
+pub fn fib_mod_p<F: FibField>(a: u64, b: u64, num_terms: usize) -> F {
+    FibIter::<F>::new(a, b)
+        .nth(num_terms.saturating_sub(1))
+        .expect("the sequence is infinite")
+}
+
+/// The terms of the sequence seeded with `a, b`, computed in `F`.
+#[derive(Clone, Debug)]
+pub struct FibIter<F: Field> {
+    a: F,
+    b: F,
+}
+
+impl<F: FibField> FibIter<F> {
+    pub fn new(a: u64, b: u64) -> Self {
+        Self {
+            a: F::from(a),
+            b: F::from(b),
+        }
+    }
+}
+
+impl<F: Field> FibIter<F> {
+    /// The sequence seeded with field elements, for seeds beyond `u64`.
+    pub fn from_seeds(a: F, b: F) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<F: Field> Iterator for FibIter<F> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<F> {
+        let term = self.a;
+        (self.a, self.b) = (self.b, self.a + self.b);
+        Some(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::group::ff::PrimeField;
+    use halo2_proofs::pasta::{Fp, Fq};
+
+    use super::*;
+
+    #[test]
+    fn test_references_over_two_fields() {
+        assert_eq!(fib_mod_p::<Fp>(1, 1, 10), Fp::from(55));
+        assert_eq!(fib_mod_p::<Fq>(1, 1, 10), Fq::from(55));
+
+        let expected: Vec<Fq> = (1..=12).map(|n| Fq::from(fib_u64(n))).collect();
+        assert_eq!(
+            FibIter::<Fq>::new(1, 1).take(12).collect::<Vec<_>>(),
+            expected
+        );
+
+        // the two moduli differ, so the terms diverge once they wrap
+        let fp = fib_mod_p::<Fp>(1, 1, 400).to_repr();
+        let fq = fib_mod_p::<Fq>(1, 1, 400).to_repr();
+        assert_ne!(fp, fq);
+    }
+}
@@ -0,0 +1,165 @@
+/*
+
+    The sequence plus one row checking a public linear relation on the seeds. The
+    coefficients and the right-hand side come from the instance, at rows 1..=3:
+
+    | elem_1 | elem_2 | elem_3 | coeff_x | coeff_y | q_relation | instance
+    -----------------------------------------------------------------------
+    |    a   |    b   |    c   |    x    |    y    |      1     |  F(n)
+    |        |        |        |         |         |            |   x
+    |        |        |        |         |         |            |   y
+    |        |        |        |         |         |            |   c
+
+    q_relation * (coeff_x * elem_1 + coeff_y * elem_2 - elem_3) = 0
+
+    a and b are copied from the first row of the sequence.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::fibonacci::{Config, FibonacciCircuit};
+
+#[derive(Clone, Debug)]
+pub struct SeedRelationConfig {
+    fib: Config,
+    coeff_x: Column<Advice>,
+    coeff_y: Column<Advice>,
+    q_relation: Selector,
+}
+
+impl SeedRelationConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let coeff_x = cs.advice_column();
+        cs.enable_equality(coeff_x);
+        let coeff_y = cs.advice_column();
+        cs.enable_equality(coeff_y);
+        let q_relation = cs.selector();
+
+        cs.create_gate("seed relation", |virtual_cells| {
+            let q_relation = virtual_cells.query_selector(q_relation);
+            let a = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let b = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let c = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let x = virtual_cells.query_advice(coeff_x, Rotation::cur());
+            let y = virtual_cells.query_advice(coeff_y, Rotation::cur());
+            vec![q_relation * (x * a + y * b - c)]
+        });
+
+        Self {
+            fib,
+            coeff_x,
+            coeff_y,
+            q_relation,
+        }
+    }
+}
+
+/// A [`FibonacciCircuit`] whose seeds must satisfy `x * a + y * b = c`, exposing
+/// `[F(n), x, y, c]`; built by [`FibonacciCircuit::with_seed_relation`]. The exposure flags
+/// of the inner circuit are ignored.
+#[derive(Clone, Debug)]
+pub struct SeedRelationCircuit<F: FieldExt> {
+    circuit: FibonacciCircuit<F>,
+    x: F,
+    y: F,
+    c: F,
+}
+
+impl<F: FieldExt> SeedRelationCircuit<F> {
+    pub(crate) fn new(circuit: FibonacciCircuit<F>, x: F, y: F, c: F) -> Self {
+        Self { circuit, x, y, c }
+    }
+
+    /// `[F(n), x, y, c]`, or `None` for a circuit without witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        Some(vec![self.circuit.output()?, self.x, self.y, self.c])
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for SeedRelationCircuit<F> {
+    type Config = SeedRelationConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        SeedRelationConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let (elem_1, elem_2) = self.circuit.seeds();
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            elem_1,
+            elem_2,
+            self.circuit.num_terms(),
+        )?;
+
+        layouter.assign_region(
+            || "seed relation",
+            |mut region| {
+                let offset = 0;
+                config.q_relation.enable(&mut region, offset)?;
+                terms[0].copy_advice(|| "a", &mut region, fib.elem_1, offset)?;
+                terms[1].copy_advice(|| "b", &mut region, fib.elem_2, offset)?;
+                region.assign_advice_from_instance(
+                    || "x",
+                    fib.instance,
+                    1,
+                    config.coeff_x,
+                    offset,
+                )?;
+                region.assign_advice_from_instance(
+                    || "y",
+                    fib.instance,
+                    2,
+                    config.coeff_y,
+                    offset,
+                )?;
+                region.assign_advice_from_instance(|| "c", fib.instance, 3, fib.elem_3, offset)?;
+                Ok(())
+            },
+        )?;
+
+        fib.expose_public(layouter, &terms[terms.len() - 1], 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_seed_relation() {
+        // 2 * 3 + 3 * 4 = 18
+        let (x, y, c) = (Fp::from(2), Fp::from(3), Fp::from(18));
+        let circuit = FibonacciCircuit::<Fp>::new(3, 4, 8).with_seed_relation(x, y, c);
+        let public_inputs = circuit.public_inputs().unwrap();
+        assert_eq!(public_inputs, vec![Fp::from(76), x, y, c]);
+        let prover = MockProver::run(5, &circuit, vec![public_inputs]).unwrap();
+        prover.assert_satisfied();
+
+        let circuit = FibonacciCircuit::<Fp>::new(3, 5, 8).with_seed_relation(x, y, c);
+        let prover = MockProver::run(5, &circuit, vec![circuit.public_inputs().unwrap()]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
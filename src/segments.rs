@@ -0,0 +1,116 @@
+//! Splitting one long sequence into a chain of proofs. Every segment runs in boundary
+//! mode (`[elem_1, elem_2, F(n - 1), F(n)]` public), and segment `i + 1` is seeded with
+//! the last two terms of segment `i`, so the chain is continuous exactly when each
+//! segment's seeds equal the previous segment's tail. A single continuation from a
+//! public checkpoint is proved in seeds-public mode instead.
+
+use halo2_proofs::pasta::Fp;
+
+use crate::error::FibError;
+use crate::fibonacci::{min_k, FibonacciCircuit};
+use crate::proof::{prove, verify_against, FibonacciProof};
+
+/// Proves the first `total` terms of the sequence seeded with `seeds` as a chain of
+/// proofs, each contributing up to `segment_len` new terms. The first segment's seeds
+/// count towards its terms; later segments start from the previous two terms.
+pub fn prove_segments(
+    seeds: (u64, u64),
+    total: usize,
+    segment_len: usize,
+) -> Result<Vec<FibonacciProof>, FibError> {
+    if total < 3 || segment_len < 3 {
+        return Err(FibError::BadPublicInputs(format!(
+            "need at least 3 terms in total and per segment, got {} and {}",
+            total, segment_len
+        )));
+    }
+
+    let first = segment_len.min(total);
+    let mut circuit = FibonacciCircuit::new(seeds.0, seeds.1, first).expose_boundary(true);
+    let mut proofs = vec![prove(&circuit, min_k(first))?];
+    let mut done = first;
+
+    while done < total {
+        let new_terms = segment_len.min(total - done);
+        let tail = &proofs.last().unwrap().public_inputs;
+        let num_terms = new_terms + 2;
+        circuit = FibonacciCircuit::from_seeds(tail[2], tail[3], num_terms).expose_boundary(true);
+        proofs.push(prove(&circuit, min_k(num_terms))?);
+        done += new_terms;
+    }
+    Ok(proofs)
+}
+
+/// Verifies every segment and that consecutive segments share their boundary terms.
+/// Returns the final term of the chain.
+pub fn verify_segments(proofs: &[FibonacciProof]) -> Result<Fp, FibError> {
+    let shape = |num_terms| FibonacciCircuit::<Fp>::new(0, 0, num_terms).expose_boundary(true);
+    for proof in proofs {
+        verify_against(&shape(proof.num_terms), proof)?;
+    }
+    for (i, pair) in proofs.windows(2).enumerate() {
+        if pair[0].public_inputs[2..] != pair[1].public_inputs[..2] {
+            return Err(FibError::BadPublicInputs(format!(
+                "segment {} does not start where segment {} ends",
+                i + 1,
+                i
+            )));
+        }
+    }
+    proofs
+        .last()
+        .map(|proof| proof.public_inputs[3])
+        .ok_or_else(|| FibError::BadPublicInputs("no segments".to_string()))
+}
+
+/// Proves `extra_terms` more terms after a public checkpoint `(F(m), F(m + 1))`, so a
+/// proof of the prefix up to the checkpoint can be composed with this one. The public
+/// inputs are `[F(m), F(m + 1), F(m + 1 + extra_terms)]`.
+pub fn prove_continuation(
+    checkpoint: (Fp, Fp),
+    extra_terms: usize,
+) -> Result<FibonacciProof, FibError> {
+    let num_terms = extra_terms + 2;
+    let circuit =
+        FibonacciCircuit::from_seeds(checkpoint.0, checkpoint.1, num_terms).expose_seeds(true);
+    prove(&circuit, min_k(num_terms))
+}
+
+/// Verifies a proof produced by [`prove_continuation`] and returns its final term.
+pub fn verify_continuation(proof: &FibonacciProof) -> Result<Fp, FibError> {
+    let shape = FibonacciCircuit::<Fp>::new(0, 0, proof.num_terms).expose_seeds(true);
+    verify_against(&shape, proof)?;
+    Ok(proof.public_inputs[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_segments_are_continuous() {
+        let proofs = prove_segments((1, 1), 20, 10).unwrap();
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].public_inputs[2..], proofs[1].public_inputs[..2]);
+        // F(20)
+        assert_eq!(verify_segments(&proofs).unwrap(), Fp::from(6765));
+    }
+
+    #[test]
+    fn test_discontinuous_segments_rejected() {
+        let mut proofs = prove_segments((1, 1), 20, 10).unwrap();
+        proofs.swap(0, 1);
+        assert!(matches!(
+            verify_segments(&proofs),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+
+    #[test]
+    fn test_continuation_from_f10() {
+        // (F(10), F(11)) -> F(20)
+        let proof = prove_continuation((Fp::from(55), Fp::from(89)), 9).unwrap();
+        assert_eq!(proof.public_inputs[..2], [Fp::from(55), Fp::from(89)]);
+        assert_eq!(verify_continuation(&proof).unwrap(), Fp::from(6765));
+    }
+}
@@ -0,0 +1,283 @@
+/*
+
+    Proves a witnessed reordering of the first n terms is a permutation of them, exposing
+    only a hash chain (see the hash chain layout) over the reordered terms. halo2 0.2 has
+    no shuffle argument, so the multiset check is the classic product one: the two lists
+    agree as multisets iff prod (g - f_i) = prod (g - s_i) as polynomials in g, which is
+    checked at a single point g. The point is the digest itself, fixed only once the
+    reordering is, as in Fiat-Shamir:
+
+    | elem_1 | elem_2 | elem_3 | q_first | q_product
+    ------------------------------------------------
+    |   x_0  |    g   |  p_0   |    1    |    0
+    |   x_1  |    g   |  p_1   |    0    |    1
+    |  ...   |   ...  |  ...   |   ...   |   ...
+    | x_n-1  |    g   | p_n-1  |    0    |    1
+
+    q_first * (p - (g - x)) = 0
+    q_product * (p - p(prev) * (g - x)) = 0
+
+    laid out once over the canonical terms and once over the reordered ones, with the two
+    final products copied together. The seeds are public too, so the canonical terms are
+    fixed before the point is known. The soundness of the check
+    rests on the digest behaving like a random oracle, which the toy compression of the
+    hash chain does not; it is a demonstration of the technique only.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::FibonacciCircuit;
+use crate::hash_chain::{hash_chain, HashChainConfig};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+use crate::reference::FibIter;
+
+#[derive(Clone, Debug)]
+pub struct ShuffleConfig {
+    chain: HashChainConfig,
+    q_first: Selector,
+    q_product: Selector,
+}
+
+impl ShuffleConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let chain = HashChainConfig::configure(cs);
+        let fib = chain.fib;
+        let q_first = cs.selector();
+        let q_product = cs.selector();
+
+        cs.create_gate("product start", |virtual_cells| {
+            let q_first = virtual_cells.query_selector(q_first);
+            let x = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let g = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let p = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_first * (p - (g - x))]
+        });
+
+        cs.create_gate("product step", |virtual_cells| {
+            let q_product = virtual_cells.query_selector(q_product);
+            let x = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let g = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let p = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let p_prev = virtual_cells.query_advice(fib.elem_3, Rotation::prev());
+            vec![q_product * (p - p_prev * (g - x))]
+        });
+
+        Self {
+            chain,
+            q_first,
+            q_product,
+        }
+    }
+
+    /// Lays out the running product of `point - x` over `cells` and returns the cell
+    /// holding the full product.
+    fn assign_product<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[AssignedCell<F, F>],
+        point: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let fib = &self.chain.fib;
+        layouter.assign_region(
+            || "product",
+            |mut region| {
+                let mut product = Value::known(F::one());
+                let mut last = None;
+                for (offset, cell) in cells.iter().enumerate() {
+                    if offset == 0 {
+                        self.q_first.enable(&mut region, offset)?;
+                    } else {
+                        self.q_product.enable(&mut region, offset)?;
+                    }
+                    let x = cell.copy_advice(|| "x", &mut region, fib.elem_1, offset)?;
+                    let g = point.copy_advice(|| "point", &mut region, fib.elem_2, offset)?;
+                    product = product
+                        .zip(g.value().zip(x.value()))
+                        .map(|(product, (&g, &x))| product * (g - x));
+                    last = Some(region.assign_advice(|| "p", fib.elem_3, offset, || product)?);
+                }
+                Ok(last.expect("at least one term"))
+            },
+        )
+    }
+}
+
+/// Circuit proving that a private reordering of the first `num_terms` terms of the
+/// sequence seeded with `a, b` is a permutation of them. The public inputs are
+/// `[digest, a, b]`, where `digest` is the hash chain over the reordered terms.
+#[derive(Clone, Debug)]
+pub struct ShuffleCircuit<F: FieldExt> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    shuffled: Option<Vec<F>>,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> ShuffleCircuit<F> {
+    /// Reorders the terms so that `order[i]` is the (0-based) position of the term placed
+    /// at position `i`. `order` has to be a permutation of `0..num_terms`.
+    pub fn new(a: u64, b: u64, order: &[usize]) -> Result<Self, FibError> {
+        let num_terms = order.len();
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "the shuffle needs at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        let mut seen = vec![false; num_terms];
+        for &i in order {
+            if i >= num_terms || std::mem::replace(&mut seen[i], true) {
+                return Err(FibError::BadPublicInputs(format!(
+                    "{:?} is not a permutation of 0..{}",
+                    order, num_terms
+                )));
+            }
+        }
+        FibonacciCircuit::<F>::new(a, b, num_terms).validate()?;
+        let terms: Vec<F> = FibIter::new(a, b).take(num_terms).collect();
+        Ok(Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            shuffled: Some(order.iter().map(|&i| terms[i]).collect()),
+            num_terms,
+        })
+    }
+
+    /// The hash chain over the reordered terms, or `None` without witnesses.
+    pub fn digest(&self) -> Option<F> {
+        self.shuffled.as_deref().map(hash_chain)
+    }
+
+    /// The canonical rows, then one row per term for the reordered terms, their hash
+    /// chain and each of the two products.
+    fn k(&self) -> u32 {
+        k_for_rows(self.num_terms - 2 + 4 * self.num_terms)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ShuffleCircuit<F> {
+    type Config = ShuffleConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            shuffled: None,
+            num_terms: self.num_terms,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ShuffleConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.chain.fib;
+        let terms = fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            self.elem_1,
+            self.elem_2,
+            self.num_terms,
+        )?;
+
+        let shuffled = layouter.assign_region(
+            || "shuffled",
+            |mut region| {
+                (0..self.num_terms)
+                    .map(|offset| {
+                        let term = match &self.shuffled {
+                            Some(shuffled) => Value::known(shuffled[offset]),
+                            None => Value::unknown(),
+                        };
+                        region.assign_advice(|| "term", fib.elem_1, offset, || term)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let digest = config
+            .chain
+            .assign_chain(layouter.namespace(|| "commitment"), &shuffled)?;
+        let canonical =
+            config.assign_product(layouter.namespace(|| "canonical"), &terms, &digest)?;
+        let reordered =
+            config.assign_product(layouter.namespace(|| "reordered"), &shuffled, &digest)?;
+        layouter.assign_region(
+            || "same multiset",
+            |mut region| region.constrain_equal(canonical.cell(), reordered.cell()),
+        )?;
+
+        fib.expose_public(layouter.namespace(|| "digest"), &digest, 0)?;
+        fib.expose_public(layouter.namespace(|| "seed a"), &terms[0], 1)?;
+        fib.expose_public(layouter.namespace(|| "seed b"), &terms[1], 2)
+    }
+}
+
+/// Proves that reordering the first `order.len()` terms seeded with `a, b` by `order` is a
+/// permutation of them, exposing the seeds and a hash chain over the reordered terms.
+pub fn prove_shuffle(a: u64, b: u64, order: &[usize]) -> Result<FibonacciProof, FibError> {
+    let circuit = ShuffleCircuit::<Fp>::new(a, b, order)?;
+    let digest = circuit.digest().expect("the reordering is known");
+    let public_inputs = vec![digest, Fp::from(a), Fp::from(b)];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: order.len(),
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_shuffle`].
+pub fn verify_shuffle(proof: &FibonacciProof) -> Result<(), FibError> {
+    let order: Vec<usize> = (0..proof.num_terms).collect();
+    let circuit = ShuffleCircuit::<Fp>::new(0, 1, &order)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let order = [4, 0, 7, 2, 9, 1, 3, 8, 6, 5];
+        let proof = prove_shuffle(1, 1, &order).unwrap();
+        verify_shuffle(&proof).unwrap();
+
+        // 5, 1, 21, 2, 55, 1, 3, 34, 13, 8
+        let shuffled: Vec<Fp> = [5, 1, 21, 2, 55, 1, 3, 34, 13, 8].map(Fp::from).to_vec();
+        assert_eq!(proof.public_inputs[0], hash_chain(&shuffled));
+
+        // the same length, but 1 appears once and 2 twice
+        let mut forged = shuffled;
+        forged[1] = Fp::from(2);
+        let circuit = ShuffleCircuit {
+            shuffled: Some(forged.clone()),
+            ..ShuffleCircuit::new(1, 1, &order).unwrap()
+        };
+        let public_inputs = vec![hash_chain(&forged), Fp::one(), Fp::one()];
+        let prover = MockProver::run(circuit.k(), &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+
+        assert!(matches!(
+            ShuffleCircuit::<Fp>::new(1, 1, &[0, 1, 1]),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+/*
+
+    Rows switch between two gates: the usual sum, or the difference elem_1 - elem_2.
+    Alternating them from 1, 1 gives
+
+    1, 1, 2, -1, 1, -2, -1, ...
+
+    | elem_1 | elem_2 | elem_3 | q_fib | q_sub
+    -------------------------------------------
+    |    1   |    1   |    2   |   1   |   0
+    |    1   |    2   |   -1   |   0   |   1
+    |    2   |   -1   |    1   |   1   |   0
+
+    q_fib * (elem_1 + elem_2 - elem_3) = 0
+    q_sub * (elem_1 - elem_2 - elem_3) = 0
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{known, Config};
+
+/// Which gate a row uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// `elem_3 = elem_1 + elem_2`
+    Add,
+    /// `elem_3 = elem_1 - elem_2`
+    Sub,
+}
+
+#[derive(Clone, Debug)]
+pub struct SignedConfig {
+    fib: Config,
+    q_sub: Selector,
+}
+
+impl SignedConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let q_sub = cs.selector();
+
+        cs.create_gate("fibonacci difference", |virtual_cells| {
+            let q_sub = virtual_cells.query_selector(q_sub);
+            let elem_1 = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let elem_2 = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let elem_3 = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_sub * (elem_1 - elem_2 - elem_3)]
+        });
+
+        Self { fib, q_sub }
+    }
+}
+
+/// Circuit running one [`Op`] per row from the seeds and exposing the last term.
+#[derive(Clone, Debug)]
+pub struct SignedCircuit<F: FieldExt> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    ops: Vec<Op>,
+}
+
+impl<F: FieldExt> SignedCircuit<F> {
+    pub fn new(a: u64, b: u64, ops: Vec<Op>) -> Result<Self, FibError> {
+        if ops.is_empty() {
+            return Err(FibError::BadPublicInputs(
+                "need at least one row".to_string(),
+            ));
+        }
+        Ok(Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            ops,
+        })
+    }
+
+    /// `num_terms` terms alternating [`Op::Add`] and [`Op::Sub`], starting with a sum.
+    pub fn alternating(a: u64, b: u64, num_terms: usize) -> Self {
+        let rows = num_terms.saturating_sub(2).max(1);
+        let ops = (0..rows)
+            .map(|row| if row % 2 == 0 { Op::Add } else { Op::Sub })
+            .collect();
+        Self::new(a, b, ops).expect("at least one row")
+    }
+
+    /// All terms, seeds included, or `None` for a circuit without witnesses.
+    pub fn terms(&self) -> Option<Vec<F>> {
+        let mut terms = vec![known(self.elem_1)?, known(self.elem_2)?];
+        for op in &self.ops {
+            let (elem_1, elem_2) = (terms[terms.len() - 2], terms[terms.len() - 1]);
+            terms.push(match op {
+                Op::Add => elem_1 + elem_2,
+                Op::Sub => elem_1 - elem_2,
+            });
+        }
+        Some(terms)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for SignedCircuit<F> {
+    type Config = SignedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ops: self.ops.clone(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        SignedConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fib = &config.fib;
+        let mut prev: Option<(AssignedCell<F, F>, AssignedCell<F, F>)> = None;
+        for op in &self.ops {
+            let row = layouter.assign_region(
+                || "signed row",
+                |mut region| {
+                    let offset = 0;
+                    match op {
+                        Op::Add => fib.q_fib.enable(&mut region, offset)?,
+                        Op::Sub => config.q_sub.enable(&mut region, offset)?,
+                    }
+
+                    let (elem_1, elem_2) = match &prev {
+                        None => (
+                            region.assign_advice(
+                                || "elem_1",
+                                fib.elem_1,
+                                offset,
+                                || self.elem_1,
+                            )?,
+                            region.assign_advice(
+                                || "elem_2",
+                                fib.elem_2,
+                                offset,
+                                || self.elem_2,
+                            )?,
+                        ),
+                        Some((elem_2, elem_3)) => (
+                            elem_2.copy_advice(|| "elem_1", &mut region, fib.elem_1, offset)?,
+                            elem_3.copy_advice(|| "elem_2", &mut region, fib.elem_2, offset)?,
+                        ),
+                    };
+                    let (x, y) = (elem_1.value().copied(), elem_2.value().copied());
+                    let elem_3 = match op {
+                        Op::Add => x + y,
+                        Op::Sub => x - y,
+                    };
+                    let elem_3 =
+                        region.assign_advice(|| "elem_3", fib.elem_3, offset, || elem_3)?;
+                    Ok((elem_2, elem_3))
+                },
+            )?;
+            prev = Some(row);
+        }
+
+        let (_, output) = prev.expect("at least one row");
+        fib.expose_public(layouter, &output, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_alternating_signs() {
+        let circuit = SignedCircuit::<Fp>::alternating(1, 1, 7);
+        let terms = circuit.terms().unwrap();
+        let expected = [
+            Fp::from(1),
+            Fp::from(1),
+            Fp::from(2),
+            -Fp::from(1),
+            Fp::from(1),
+            -Fp::from(2),
+            -Fp::from(1),
+        ];
+        assert_eq!(terms, expected);
+
+        let prover = MockProver::run(5, &circuit, vec![vec![-Fp::from(1)]]).unwrap();
+        prover.assert_satisfied();
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(1)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_no_rows_rejected() {
+        assert!(matches!(
+            SignedCircuit::<Fp>::new(1, 1, vec![]),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+/*
+
+    F(1)^2 + F(2)^2 + ... + F(n)^2 = F(n) * F(n + 1)
+
+    The sequence is laid out up to F(n + 1), then every term up to F(n) is copied into a
+    second region next to a running sum of squares; the last row also holds F(n + 1):
+
+    | elem_1 |  elem_2  |  elem_3  | q_first | q_step | q_product
+    -------------------------------------------------------------
+    |  F(1)  |          |  acc_1   |    1    |   0    |    0
+    |  F(2)  |          |  acc_2   |    0    |   1    |    0
+    |  ...   |          |   ...    |    0    |   1    |    0
+    |  F(n)  | F(n + 1) |  acc_n   |    0    |   1    |    1
+
+    q_first * (acc - elem_1^2) = 0
+    q_step * (acc - acc(prev) - elem_1^2) = 0
+    q_product * (acc - elem_1 * elem_2) = 0
+
+    The identity holds for any multiple of the sequence too, so both seeds are pinned to 1.
+    The sum is the public input.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{Config, FibonacciCircuit};
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct SquaresConfig {
+    fib: Config,
+    q_first: Selector,
+    q_step: Selector,
+    q_product: Selector,
+}
+
+impl SquaresConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        // pins the seeds
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let q_first = cs.selector();
+        let q_step = cs.selector();
+        let q_product = cs.selector();
+
+        cs.create_gate("sum of squares start", |virtual_cells| {
+            let q_first = virtual_cells.query_selector(q_first);
+            let term = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let acc = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_first * (acc - term.clone() * term)]
+        });
+
+        // a gate of its own, as the first row has no previous sum to query
+        cs.create_gate("sum of squares step", |virtual_cells| {
+            let q_step = virtual_cells.query_selector(q_step);
+            let term = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let acc = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let prev_acc = virtual_cells.query_advice(fib.elem_3, Rotation::prev());
+            vec![q_step * (acc - prev_acc - term.clone() * term)]
+        });
+
+        cs.create_gate("square product", |virtual_cells| {
+            let q_product = virtual_cells.query_selector(q_product);
+            let f_n = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let f_n_1 = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let acc = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![q_product * (acc - f_n * f_n_1)]
+        });
+
+        Self {
+            fib,
+            q_first,
+            q_step,
+            q_product,
+        }
+    }
+}
+
+/// Circuit proving the sum-of-squares identity for the first `n` terms of the standard
+/// `1, 1` sequence, exposing the sum.
+#[derive(Clone, Debug)]
+pub struct SquaresCircuit {
+    n: usize,
+    // witnessed for both seeds; only a test hands in anything but 1
+    seed: u64,
+    witness: bool,
+}
+
+impl SquaresCircuit {
+    pub fn new(n: usize) -> Result<Self, FibError> {
+        if n < 2 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 2 terms, got {}",
+                n
+            )));
+        }
+        Ok(Self {
+            n,
+            seed: 1,
+            witness: true,
+        })
+    }
+
+    /// `F(n) * F(n + 1)`, the public input.
+    pub fn output(&self) -> Fp {
+        let (_, f_n, f_n_1) = FibonacciCircuit::<Fp>::new(1, 1, self.n + 1)
+            .last_row()
+            .expect("seeds are known");
+        f_n * f_n_1
+    }
+
+    fn k(&self) -> u32 {
+        // the sequence rows up to F(n + 1) plus one row per square
+        k_for_rows(self.n - 1 + self.n)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for SquaresCircuit {
+    type Config = SquaresConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        SquaresConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let seed = if self.witness {
+            Value::known(F::from(self.seed))
+        } else {
+            Value::unknown()
+        };
+        let terms =
+            config
+                .fib
+                .assign_terms(layouter.namespace(|| "sequence"), seed, seed, self.n + 1)?;
+
+        let sum = layouter.assign_region(
+            || "sum of squares",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::one())?;
+
+                let mut acc = Value::known(F::zero());
+                let mut sum = None;
+                for (offset, term) in terms[..self.n].iter().enumerate() {
+                    if offset == 0 {
+                        config.q_first.enable(&mut region, offset)?;
+                    } else {
+                        config.q_step.enable(&mut region, offset)?;
+                    }
+                    let term =
+                        term.copy_advice(|| "F(i)", &mut region, config.fib.elem_1, offset)?;
+                    let square = term.value().map(|term| term.square());
+                    acc = acc + square;
+                    sum =
+                        Some(region.assign_advice(|| "acc", config.fib.elem_3, offset, || acc)?);
+                }
+
+                let last = self.n - 1;
+                config.q_product.enable(&mut region, last)?;
+                terms[self.n].copy_advice(|| "F(n + 1)", &mut region, config.fib.elem_2, last)?;
+                Ok(sum.expect("at least two terms"))
+            },
+        )?;
+
+        config.fib.expose_public(layouter, &sum, 0)
+    }
+}
+
+/// Proves `F(1)^2 + ... + F(n)^2 = F(n) * F(n + 1)`.
+pub fn prove_sum_of_squares(n: usize) -> Result<FibonacciProof, FibError> {
+    let circuit = SquaresCircuit::new(n)?;
+    let public_inputs = vec![circuit.output()];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: n,
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_sum_of_squares`].
+pub fn verify_sum_of_squares(proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = SquaresCircuit::new(proof.num_terms)?;
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+    use crate::reference::fib_u64;
+
+    #[test]
+    fn test_sum_of_squares_10() {
+        let squares: u64 = (1..=10).map(|i| fib_u64(i) * fib_u64(i)).sum();
+        assert_eq!(squares, 4895);
+        assert_eq!(fib_u64(10) * fib_u64(11), 4895);
+
+        let proof = prove_sum_of_squares(10).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(4895)]);
+        verify_sum_of_squares(&proof).unwrap();
+
+        let circuit = SquaresCircuit::new(10).unwrap();
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(4894)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_forged_seeds_rejected() {
+        let circuit = SquaresCircuit::new(10).unwrap();
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(4895)]]).unwrap();
+        prover.assert_satisfied();
+
+        // seeds 2, 2 scale both sides by 4
+        let circuit = SquaresCircuit {
+            seed: 2,
+            ..SquaresCircuit::new(10).unwrap()
+        };
+        let instance = vec![Fp::from(4 * 4895)];
+        let prover = MockProver::run(circuit.k(), &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
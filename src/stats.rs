@@ -0,0 +1,330 @@
+//! Layout statistics gathered by synthesizing a circuit against a recording backend,
+//! without building keys or proofs.
+
+use halo2_proofs::arithmetic::{Field, FieldExt};
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::{CircuitCost, CircuitGates};
+use halo2_proofs::pasta::{Eq, Fp};
+use halo2_proofs::plonk::{
+    Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+    FloorPlanner, Instance, Selector,
+};
+
+use crate::error::FibError;
+use crate::fibonacci::{min_k, Config, FibonacciCircuit};
+
+/// Counters recorded while synthesizing a circuit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Copy constraints between advice cells (`copy_advice`).
+    pub advice_copies: usize,
+    /// Copy constraints into the instance column (`constrain_instance`).
+    pub instance_copies: usize,
+    /// Rows with at least one selector enabled, in ascending order.
+    pub selector_rows: Vec<usize>,
+    /// Advice cells assigned, copies included.
+    pub advice_cells: usize,
+}
+
+impl CircuitStats {
+    /// Synthesizes `circuit` and records its layout statistics.
+    pub fn measure<F: Field, C: Circuit<F>>(circuit: &C) -> Result<Self, FibError> {
+        let mut cs = ConstraintSystem::default();
+        let config = C::configure(&mut cs);
+        let mut recorder = Recorder::default();
+        C::FloorPlanner::synthesize(&mut recorder, circuit, config, vec![])?;
+        let mut stats = recorder.stats;
+        stats.selector_rows.sort_unstable();
+        stats.selector_rows.dedup();
+        Ok(stats)
+    }
+}
+
+/// Number of `copy_advice` calls made by a `num_terms` Fibonacci circuit: two per row
+/// after the seed row, i.e. `2 * (num_terms - 3)`.
+pub fn copy_count(num_terms: usize) -> Result<usize, FibError> {
+    let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms);
+    Ok(CircuitStats::measure(&circuit)?.advice_copies)
+}
+
+/// The rows on which a `num_terms` Fibonacci circuit enables `q_fib`: one per row of the
+/// layout, so `0..num_terms - 2`.
+pub fn active_selector_rows(num_terms: usize) -> Result<Vec<usize>, FibError> {
+    let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms);
+    Ok(CircuitStats::measure(&circuit)?.selector_rows)
+}
+
+/// Number of advice cells a `num_terms` Fibonacci circuit assigns, to size its witness:
+/// three per row, copied cells included, i.e. `3 * (num_terms - 2)` for at least 3 terms.
+pub fn assigned_cell_count(num_terms: usize) -> usize {
+    3 * (num_terms.max(3) - 2)
+}
+
+/// `(degree, number of gates)` of the Fibonacci constraint system, where the degree is
+/// the highest of its gate polynomials.
+///
+/// That is the degree of the constraints themselves, 2 for the `fibonacci` gate. The
+/// proving system supports at least degree 3 anyway, since halo2 folds the permutation
+/// argument in, which is what `ConstraintSystem::degree` reports.
+pub fn constraint_system_info<F: FieldExt>() -> (usize, usize) {
+    // `ConstraintSystem` keeps its gates private, so the degree is taken from the gate as
+    // the chip builds it
+    let degree = std::cell::Cell::new(0);
+    Config::configure_custom(
+        &mut ConstraintSystem::<F>::default(),
+        |cells, elem_1, elem_2, elem_3, q_fib| {
+            let gate = Config::fibonacci_gate(cells, elem_1, elem_2, elem_3, q_fib);
+            degree.set(gate.degree());
+            gate
+        },
+    );
+
+    // and `CircuitGates` reports their count
+    let gates = CircuitGates::collect::<F, FibonacciCircuit<F>>().to_string();
+    let num_gates = gates
+        .lines()
+        .find_map(|line| line.strip_prefix("Total gates: "))
+        .and_then(|count| count.parse().ok())
+        .unwrap_or_else(|| panic!("`CircuitGates` reports no gate count: {}", gates));
+    (degree.get(), num_gates)
+}
+
+/// Number of columns taking part in the permutation (copy-constraint) argument of
+/// `circuit` at size `k`.
+pub fn permutation_columns<C: Circuit<Fp> + std::fmt::Debug>(circuit: &C, k: u32) -> usize {
+    circuit_cost_field(circuit, k, "permutation_cols")
+}
+
+/// Number of advice columns circuits of type `C` configure.
+pub fn advice_columns<F: Field, C: Circuit<F>>() -> usize {
+    let mut cs = ConstraintSystem::<F>::default();
+    C::configure(&mut cs);
+    // column indices are private, but columns compare by them: a fresh system numbers its
+    // columns from 0, so the count is how many it allocates before the next free one here
+    let next = cs.advice_column();
+    let mut fresh = ConstraintSystem::<F>::default();
+    (0..).take_while(|_| fresh.advice_column() != next).count()
+}
+
+/// One count from `CircuitCost`, which only exposes them through `Debug`. Panics if the
+/// field is missing or not a count, i.e. if a `halo2_proofs` upgrade changed the format.
+fn circuit_cost_field<C: Circuit<Fp> + std::fmt::Debug>(circuit: &C, k: u32, field: &str) -> usize {
+    let cost = format!("{:?}", CircuitCost::<Eq, C>::measure(k as usize, circuit));
+    let count = cost
+        .split(&format!("{}: ", field))
+        .nth(1)
+        .and_then(|rest| rest.split([',', ' ', '}']).next())
+        .unwrap_or_else(|| panic!("`CircuitCost` has no `{}` field: {}", field, cost));
+    count
+        .parse()
+        .unwrap_or_else(|_| panic!("`CircuitCost` field `{}` is not a count: {}", field, cost))
+}
+
+/// Rough proving cost, bucketed by circuit size since wall-clock time depends on the
+/// machine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProvingTimeClass {
+    /// `k <= 10`: well under a second.
+    Fast,
+    /// `k <= 16`: seconds.
+    Moderate,
+    /// Larger circuits: minutes and up.
+    Slow,
+}
+
+/// What proving a `num_terms` Fibonacci circuit at its minimal `k` would cost, from
+/// halo2's cost model rather than an actual proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub k: u32,
+    /// Estimated size of the proof bytes.
+    pub proof_bytes: usize,
+    pub proving_time: ProvingTimeClass,
+    /// Size of the multi-scalar multiplication the IPA verifier ends with, which dominates
+    /// verification: one point per row.
+    pub verifier_msm_size: usize,
+}
+
+/// Estimates the cost of proving a `num_terms` Fibonacci circuit without proving it.
+pub fn estimate_cost(num_terms: usize) -> Result<CostEstimate, FibError> {
+    let circuit = FibonacciCircuit::<Fp>::new(1, 1, num_terms);
+    circuit.validate()?;
+    let k = min_k(num_terms);
+    let cost = CircuitCost::<Eq, _>::measure(k as usize, &circuit);
+    let proving_time = match k {
+        0..=10 => ProvingTimeClass::Fast,
+        11..=16 => ProvingTimeClass::Moderate,
+        _ => ProvingTimeClass::Slow,
+    };
+    Ok(CostEstimate {
+        k,
+        proof_bytes: cost.proof_size(circuit.num_public_inputs()).into(),
+        proving_time,
+        verifier_msm_size: 1 << k,
+    })
+}
+
+#[derive(Default)]
+struct Recorder {
+    stats: CircuitStats,
+}
+
+impl<F: Field> Assignment<F> for Recorder {
+    fn enter_region<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.stats.selector_rows.push(row);
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        _: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.stats.advice_cells += 1;
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        _: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        left: Column<Any>,
+        _: usize,
+        right: Column<Any>,
+        _: usize,
+    ) -> Result<(), Error> {
+        match (left.column_type(), right.column_type()) {
+            (Any::Instance, _) | (_, Any::Instance) => self.stats.instance_copies += 1,
+            _ => self.stats.advice_copies += 1,
+        }
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::BudgetedCircuit;
+
+    #[test]
+    fn test_copy_count() {
+        for num_terms in [3, 4, 10, 20] {
+            assert_eq!(copy_count(num_terms).unwrap(), 2 * (num_terms - 3));
+        }
+
+        let stats = CircuitStats::measure(&FibonacciCircuit::<Fp>::new(1, 1, 10)).unwrap();
+        assert_eq!(stats.instance_copies, 1);
+    }
+
+    #[test]
+    fn test_assigned_cell_count() {
+        for num_terms in [3, 4, 10, 20] {
+            let stats =
+                CircuitStats::measure(&FibonacciCircuit::<Fp>::new(1, 1, num_terms)).unwrap();
+            assert_eq!(stats.advice_cells, assigned_cell_count(num_terms));
+
+            // the narrowest layout there is, two columns with one extra row for the output
+            let two = BudgetedCircuit::<Fp, 2>::new(1, 1, num_terms);
+            let stats = CircuitStats::measure(&two).unwrap();
+            assert_eq!(stats.advice_cells, 2 * (num_terms - 1));
+        }
+        assert_eq!(assigned_cell_count(10), 24);
+    }
+
+    #[test]
+    fn test_active_selector_rows() {
+        let rows = active_selector_rows(10).unwrap();
+        assert_eq!(rows, (0..8).collect::<Vec<_>>());
+        // the row after the last term is left off
+        assert!(!rows.contains(&8));
+    }
+
+    #[test]
+    fn test_estimate_cost_scales_with_num_terms() {
+        let estimates: Vec<_> = [10, 100, 1000, 100_000]
+            .iter()
+            .map(|&num_terms| estimate_cost(num_terms).unwrap())
+            .collect();
+        for pair in estimates.windows(2) {
+            assert!(pair[0].k < pair[1].k);
+            assert!(pair[0].proof_bytes < pair[1].proof_bytes);
+            assert!(pair[0].verifier_msm_size < pair[1].verifier_msm_size);
+            assert!(pair[0].proving_time <= pair[1].proving_time);
+        }
+        assert_eq!(estimates[0].proving_time, ProvingTimeClass::Fast);
+        assert_eq!(estimates[3].proving_time, ProvingTimeClass::Slow);
+
+        // the model is an upper bound within a couple of group elements of the real size
+        let proof = crate::proof::prove(&FibonacciCircuit::<Fp>::new(1, 1, 10), min_k(10)).unwrap();
+        let slack = estimates[0].proof_bytes - proof.proof.len();
+        assert!(slack <= 64, "{}", slack);
+    }
+
+    #[test]
+    #[should_panic(expected = "`CircuitCost` has no `no_such_count` field")]
+    fn test_missing_cost_field_panics() {
+        circuit_cost_field(&FibonacciCircuit::<Fp>::new(1, 1, 10), 5, "no_such_count");
+    }
+
+    #[test]
+    fn test_constraint_system_info() {
+        assert_eq!(constraint_system_info::<Fp>(), (2, 1));
+    }
+}
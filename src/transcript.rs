@@ -0,0 +1,198 @@
+//! Transcript dumps for debugging proofs that unexpectedly fail to verify.
+//!
+//! [`prove_with_dump`] and [`verify_with_dump`] record every value absorbed into or
+//! squeezed from the Fiat-Shamir transcript. For a valid proof both sides see the same
+//! sequence, so when verification fails, the first event at which the two dumps differ
+//! shows where the verifier's view diverged from the prover's.
+
+use std::io;
+
+use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::pasta::group::ff::PrimeField;
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{
+    create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, SingleVerifier,
+};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::transcript::{
+    Blake2bRead, Blake2bWrite, Challenge255, EncodedChallenge, Transcript, TranscriptRead,
+    TranscriptWrite,
+};
+use rand_core::OsRng;
+
+use crate::error::FibError;
+use crate::fibonacci::FibonacciCircuit;
+use crate::proof::FibonacciProof;
+
+/// One value passing through the transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptEvent {
+    /// `"point"`, `"scalar"` or `"challenge"`.
+    pub label: &'static str,
+    /// The encoding of the value.
+    pub bytes: Vec<u8>,
+}
+
+/// Wraps a transcript, recording what passes through it. Points and scalars are recorded
+/// the same way whether they are written, read or absorbed as common values.
+struct Recording<T> {
+    inner: T,
+    events: Vec<TranscriptEvent>,
+}
+
+impl<T> Recording<T> {
+    fn new(inner: T) -> Self {
+        Self {
+            inner,
+            events: vec![],
+        }
+    }
+
+    fn record(&mut self, label: &'static str, bytes: &[u8]) {
+        self.events.push(TranscriptEvent {
+            label,
+            bytes: bytes.to_vec(),
+        });
+    }
+
+    fn record_point<C: CurveAffine>(&mut self, point: &C) {
+        self.record("point", point.to_bytes().as_ref());
+    }
+
+    fn record_scalar<C: CurveAffine>(&mut self, scalar: &C::Scalar) {
+        self.record("scalar", scalar.to_repr().as_ref());
+    }
+}
+
+impl<C: CurveAffine, E: EncodedChallenge<C>, T: Transcript<C, E>> Transcript<C, E>
+    for Recording<T>
+{
+    fn squeeze_challenge(&mut self) -> E {
+        let challenge = self.inner.squeeze_challenge();
+        self.record("challenge", challenge.get_scalar().to_repr().as_ref());
+        challenge
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        self.record_point(&point);
+        self.inner.common_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.record_scalar::<C>(&scalar);
+        self.inner.common_scalar(scalar)
+    }
+}
+
+impl<C: CurveAffine, E: EncodedChallenge<C>, T: TranscriptWrite<C, E>> TranscriptWrite<C, E>
+    for Recording<T>
+{
+    fn write_point(&mut self, point: C) -> io::Result<()> {
+        self.record_point(&point);
+        self.inner.write_point(point)
+    }
+
+    fn write_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.record_scalar::<C>(&scalar);
+        self.inner.write_scalar(scalar)
+    }
+}
+
+impl<C: CurveAffine, E: EncodedChallenge<C>, T: TranscriptRead<C, E>> TranscriptRead<C, E>
+    for Recording<T>
+{
+    fn read_point(&mut self) -> io::Result<C> {
+        let point = self.inner.read_point()?;
+        self.record_point(&point);
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        let scalar = self.inner.read_scalar()?;
+        self.record_scalar::<C>(&scalar);
+        Ok(scalar)
+    }
+}
+
+/// Like [`prove`](crate::proof::prove), also returning the prover's transcript events.
+pub fn prove_with_dump(
+    circuit: &FibonacciCircuit<Fp>,
+    k: u32,
+) -> Result<(FibonacciProof, Vec<TranscriptEvent>), FibError> {
+    circuit.validate()?;
+    let public_inputs = circuit
+        .public_inputs()
+        .ok_or_else(|| FibError::BadPublicInputs("the circuit has no witness".to_string()))?;
+
+    let params: Params<EqAffine> = Params::new(k);
+    let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses())?;
+    let mut transcript = Recording::new(Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]));
+    create_proof(
+        &params,
+        &pk,
+        std::slice::from_ref(circuit),
+        &[&[&public_inputs]],
+        OsRng,
+        &mut transcript,
+    )?;
+
+    let proof = FibonacciProof {
+        proof: transcript.inner.finalize(),
+        public_inputs,
+        num_terms: circuit.num_terms(),
+        k,
+    };
+    Ok((proof, transcript.events))
+}
+
+/// Like [`verify`](crate::proof::verify), also returning the verifier's transcript events
+/// up to where verification stopped.
+pub fn verify_with_dump(proof: &FibonacciProof) -> (Result<(), FibError>, Vec<TranscriptEvent>) {
+    let circuit = FibonacciCircuit::<Fp>::new(0, 0, proof.num_terms);
+    if let Err(e) = circuit.check_public_inputs(&proof.public_inputs) {
+        return (Err(e), vec![]);
+    }
+    let params: Params<EqAffine> = Params::new(proof.k);
+    let vk = match keygen_vk(&params, &circuit) {
+        Ok(vk) => vk,
+        Err(e) => return (Err(e.into()), vec![]),
+    };
+
+    let strategy = SingleVerifier::new(&params);
+    let mut transcript = Recording::new(Blake2bRead::<_, _, Challenge255<_>>::init(
+        proof.proof.as_slice(),
+    ));
+    let result = verify_proof(
+        &params,
+        &vk,
+        strategy,
+        &[&[&proof.public_inputs]],
+        &mut transcript,
+    )
+    .map_err(|_| FibError::Verify);
+    (result, transcript.events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_dumps() {
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+        let (proof, proved) = prove_with_dump(&circuit, 5).unwrap();
+        let (result, verified) = verify_with_dump(&proof);
+        result.unwrap();
+        assert!(!proved.is_empty());
+        assert_eq!(proved, verified);
+
+        // a wrong public input changes what the verifier absorbs
+        let mut forged = proof;
+        forged.public_inputs[0] += Fp::one();
+        let (result, verified) = verify_with_dump(&forged);
+        assert!(matches!(result, Err(FibError::Verify)));
+        let diverged = proved.iter().zip(&verified).position(|(p, v)| p != v);
+        assert!(diverged.is_some());
+    }
+}
@@ -0,0 +1,161 @@
+/*
+
+    Exposes an affine transform of the output instead of the output itself:
+
+    | elem_1 | elem_2 | scale | offset | q_transform | instance
+    -----------------------------------------------------------
+    |  F(n)  |   y    |   s   |   t    |      1      |    y
+
+    q_transform * (elem_2 - (scale * elem_1 + offset)) = 0
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::fibonacci::{Config, FibonacciCircuit};
+
+#[derive(Clone, Debug)]
+pub struct TransformConfig {
+    fib: Config,
+    scale: Column<Fixed>,
+    offset: Column<Fixed>,
+    q_transform: Selector,
+}
+
+impl TransformConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        let scale = cs.fixed_column();
+        let offset = cs.fixed_column();
+        let q_transform = cs.selector();
+
+        cs.create_gate("output transform", |virtual_cells| {
+            let q_transform = virtual_cells.query_selector(q_transform);
+            let output = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let transformed = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let scale = virtual_cells.query_fixed(scale, Rotation::cur());
+            let offset = virtual_cells.query_fixed(offset, Rotation::cur());
+            vec![q_transform * (transformed - (scale * output + offset))]
+        });
+
+        Self {
+            fib,
+            scale,
+            offset,
+            q_transform,
+        }
+    }
+}
+
+/// A [`FibonacciCircuit`] exposing `scale * F(n) + offset` as its single public input;
+/// built by [`FibonacciCircuit::with_output_transform`]. The exposure flags of the inner
+/// circuit are ignored.
+#[derive(Clone, Debug)]
+pub struct TransformedCircuit<F: FieldExt> {
+    circuit: FibonacciCircuit<F>,
+    scale: F,
+    offset: F,
+}
+
+impl<F: FieldExt> TransformedCircuit<F> {
+    pub(crate) fn new(circuit: FibonacciCircuit<F>, scale: F, offset: F) -> Self {
+        Self {
+            circuit,
+            scale,
+            offset,
+        }
+    }
+
+    /// The transformed output, or `None` for a circuit without witnesses.
+    pub fn output(&self) -> Option<F> {
+        Some(self.scale * self.circuit.output()? + self.offset)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for TransformedCircuit<F> {
+    type Config = TransformConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        TransformConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let (elem_1, elem_2) = self.circuit.seeds();
+        let terms = config.fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            elem_1,
+            elem_2,
+            self.circuit.num_terms(),
+        )?;
+        let output = &terms[terms.len() - 1];
+
+        let transformed = layouter.assign_region(
+            || "output transform",
+            |mut region| {
+                let offset = 0;
+                config.q_transform.enable(&mut region, offset)?;
+                let output =
+                    output.copy_advice(|| "F(n)", &mut region, config.fib.elem_1, offset)?;
+                region.assign_fixed(
+                    || "scale",
+                    config.scale,
+                    offset,
+                    || Value::known(self.scale),
+                )?;
+                region.assign_fixed(
+                    || "offset",
+                    config.offset,
+                    offset,
+                    || Value::known(self.offset),
+                )?;
+                let transformed = output
+                    .value()
+                    .map(|output| self.scale * output + self.offset);
+                region.assign_advice(
+                    || "transformed output",
+                    config.fib.elem_2,
+                    offset,
+                    || transformed,
+                )
+            },
+        )?;
+
+        config.fib.expose_public(layouter, &transformed, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_doubled_output() {
+        let circuit =
+            FibonacciCircuit::<Fp>::new(1, 1, 10).with_output_transform(Fp::from(2), Fp::zero());
+        assert_eq!(circuit.output(), Some(Fp::from(110)));
+
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(110)]]).unwrap();
+        prover.assert_satisfied();
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
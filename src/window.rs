@@ -0,0 +1,258 @@
+/*
+
+    A window of the sequence starting at a public index: the seeds are F(base) and
+    F(base + 1), and the row index of the length layout starts at base + 2 instead of the
+    constant 3:
+
+    | elem_1 | elem_2 |  elem_3  |  index   | start | q_start | instance
+    ---------------------------------------------------------------------
+    |  F(b)  | F(b+1) |  F(b+2)  |   b + 2  |   b   |    1    |  F(last)
+    |  ...   |  ...   |   ...    |   ...    |       |    0    |    b
+    |        |        | F(last)  |   last   |       |    0    |   last
+    |        |        |          |          |       |         |   F(b)
+    |        |        |          |          |       |         |  F(b+1)
+
+    q_start * (index - start - 2) = 0
+
+    Nothing in the circuit ties the seeds to base, so they are exposed too, and the
+    verifier checks them against the canonical sequence with `check_seeds`. Without that
+    check the indices are only labels.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::{known, FibonacciCircuit};
+use crate::length::LengthConfig;
+use crate::reference::fib_mod_p;
+
+#[derive(Clone, Debug)]
+pub struct WindowConfig {
+    pub(crate) length: LengthConfig,
+    pub(crate) start: Column<Advice>,
+    pub(crate) q_start: Selector,
+}
+
+impl WindowConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let length = LengthConfig::configure(cs);
+        // the seeds go to the instance
+        cs.enable_equality(length.lane.elem_1);
+        cs.enable_equality(length.lane.elem_2);
+        let start = cs.advice_column();
+        cs.enable_equality(start);
+        let q_start = cs.selector();
+
+        cs.create_gate("window start", |virtual_cells| {
+            let q_start = virtual_cells.query_selector(q_start);
+            let index = virtual_cells.query_advice(length.index, Rotation::cur());
+            let start = virtual_cells.query_advice(start, Rotation::cur());
+            vec![q_start * (index - start - Expression::Constant(F::from(2)))]
+        });
+
+        Self {
+            length,
+            start,
+            q_start,
+        }
+    }
+}
+
+/// Circuit exposing `[F(last), base, last, F(base), F(base + 1)]` for `num_terms` terms
+/// starting at index `base`, so the verifier learns exactly which indices were computed
+/// once [`WindowCircuit::check_seeds`] accepts the seeds.
+#[derive(Clone, Debug)]
+pub struct WindowCircuit<F: FieldExt> {
+    elem_1: Value<F>,
+    elem_2: Value<F>,
+    base: u64,
+    num_terms: usize,
+}
+
+impl<F: FieldExt> WindowCircuit<F> {
+    /// A window whose seeds `a, b` are claimed to be the terms at indices `base` and
+    /// `base + 1`; [`Self::check_seeds`] tells whether they are.
+    pub fn new(base: u64, a: u64, b: u64, num_terms: usize) -> Result<Self, FibError> {
+        if num_terms < 3 {
+            return Err(FibError::BadPublicInputs(format!(
+                "need at least 3 terms, got {}",
+                num_terms
+            )));
+        }
+        check_indices(base, num_terms)?;
+        Ok(Self {
+            elem_1: Value::known(F::from(a)),
+            elem_2: Value::known(F::from(b)),
+            base,
+            num_terms,
+        })
+    }
+
+    /// Index of the last term in the window.
+    pub fn last_index(&self) -> u64 {
+        self.base + self.num_terms as u64 - 1
+    }
+
+    /// The public inputs `[F(last), base, last, F(base), F(base + 1)]`, or `None` without
+    /// witnesses.
+    pub fn public_inputs(&self) -> Option<Vec<F>> {
+        let (elem_1, elem_2) = (known(self.elem_1)?, known(self.elem_2)?);
+        let output = FibonacciCircuit::from_seeds(elem_1, elem_2, self.num_terms).output()?;
+        Some(vec![
+            output,
+            F::from(self.base),
+            F::from(self.last_index()),
+            elem_1,
+            elem_2,
+        ])
+    }
+
+    /// Checks the exposed seeds are `F(base)` and `F(base + 1)` of the standard `1, 1`
+    /// sequence, reduced in the field. Linear in `base`.
+    pub fn check_seeds(&self, public_inputs: &[F]) -> Result<(), FibError> {
+        let base = self.base as usize;
+        let expected = [fib_mod_p::<F>(1, 1, base), fib_mod_p::<F>(1, 1, base + 1)];
+        if public_inputs.get(3..5) != Some(&expected[..]) {
+            return Err(FibError::BadPublicInputs(format!(
+                "the seeds are not F({}) and F({})",
+                base,
+                base + 1
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects a window whose indices don't all fit in a `u64`, or that starts before
+/// `F(1)`.
+pub(crate) fn check_indices(base: u64, num_terms: usize) -> Result<(), FibError> {
+    if base == 0 || base.checked_add(num_terms as u64).is_none() {
+        return Err(FibError::BadPublicInputs(format!(
+            "a window of {} terms can't start at index {}",
+            num_terms, base
+        )));
+    }
+    Ok(())
+}
+
+impl<F: FieldExt> Circuit<F> for WindowCircuit<F> {
+    type Config = WindowConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            elem_1: Value::unknown(),
+            elem_2: Value::unknown(),
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        WindowConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let lane = config.length.lane;
+        let rows = self.num_terms - 2;
+        let (seeds, output, last) = layouter.assign_region(
+            || "window",
+            |mut region| {
+                let [seed_1, seed_2, output] =
+                    lane.assign_rows_with_seeds(&mut region, self.elem_1, self.elem_2, rows)?;
+
+                config.q_start.enable(&mut region, 0)?;
+                region.assign_advice_from_instance(|| "base", lane.instance, 1, config.start, 0)?;
+
+                let mut last = None;
+                for offset in 0..rows {
+                    let index = Value::known(F::from(self.base + 2 + offset as u64));
+                    last = Some(region.assign_advice(
+                        || "index",
+                        config.length.index,
+                        offset,
+                        || index,
+                    )?);
+                }
+                Ok(((seed_1, seed_2), output, last.expect("at least one row")))
+            },
+        )?;
+
+        layouter.constrain_instance(output.cell(), lane.instance, 0)?;
+        layouter.constrain_instance(last.cell(), lane.instance, 2)?;
+        layouter.constrain_instance(seeds.0.cell(), lane.instance, 3)?;
+        layouter.constrain_instance(seeds.1.cell(), lane.instance, 4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+    use halo2_proofs::pasta::Fp;
+
+    use super::*;
+
+    #[test]
+    fn test_window_from_index_5() {
+        // F(5) = 5, F(6) = 8, ..., F(14) = 377
+        let circuit = WindowCircuit::<Fp>::new(5, 5, 8, 10).unwrap();
+        assert_eq!(circuit.last_index(), 14);
+        let public_inputs = circuit.public_inputs().unwrap();
+        assert_eq!(
+            public_inputs,
+            vec![
+                Fp::from(377),
+                Fp::from(5),
+                Fp::from(14),
+                Fp::from(5),
+                Fp::from(8)
+            ]
+        );
+        circuit.check_seeds(&public_inputs).unwrap();
+        let prover = MockProver::run(5, &circuit, vec![public_inputs.clone()]).unwrap();
+        prover.assert_satisfied();
+
+        // the last index follows from the base, so claiming another window fails
+        let mut shifted = public_inputs.clone();
+        shifted[1] = Fp::from(6);
+        let prover = MockProver::run(5, &circuit, vec![shifted]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // the exposed seeds have to be the ones witnessed
+        let mut swapped = public_inputs;
+        swapped.swap(3, 4);
+        let prover = MockProver::run(5, &circuit, vec![swapped]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_hand_picked_seeds_rejected() {
+        // 3, 4, 7: a valid circuit claiming F(102) = 7, caught by the seed check
+        let circuit = WindowCircuit::<Fp>::new(100, 3, 4, 3).unwrap();
+        let public_inputs = circuit.public_inputs().unwrap();
+        let prover = MockProver::run(4, &circuit, vec![public_inputs.clone()]).unwrap();
+        prover.assert_satisfied();
+        assert!(matches!(
+            circuit.check_seeds(&public_inputs),
+            Err(FibError::BadPublicInputs(_))
+        ));
+    }
+
+    #[test]
+    fn test_indices_out_of_range_rejected() {
+        for base in [0, u64::MAX - 5] {
+            assert!(matches!(
+                WindowCircuit::<Fp>::new(base, 1, 1, 10),
+                Err(FibError::BadPublicInputs(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,244 @@
+/*
+
+    target = sum of non-consecutive Fibonacci numbers (Zeckendorf's theorem)
+
+    The sequence 1, 2, 3, 5, ... (seeds pinned to the constants 1, 2) is laid out as
+    usual, then every term is copied into a second region next to a selection bit and a
+    running sum:
+
+    | elem_1 | elem_2 |  elem_3  | q_first | q_step
+    ------------------------------------------------
+    |  F(2)  |  bit_0 |  acc_0   |    1    |   0
+    |  F(3)  |  bit_1 |  acc_1   |    0    |   1
+    |  ...   |  ...   |   ...    |    0    |   1
+
+    q_first * (bit * (1 - bit)) = 0
+    q_first * (acc - bit * term) = 0
+    q_step * (bit * (1 - bit)) = 0
+    q_step * (bit * bit(prev)) = 0
+    q_step * (acc - acc(prev) - bit * term) = 0
+
+    The final running sum is the public input.
+
+*/
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::*;
+use halo2_proofs::poly::Rotation;
+
+use crate::error::FibError;
+use crate::fibonacci::Config;
+use crate::proof::{create_circuit_proof, k_for_rows, verify_circuit_proof, FibonacciProof};
+
+#[derive(Clone, Debug)]
+pub struct ZeckendorfConfig {
+    fib: Config,
+    q_first: Selector,
+    q_step: Selector,
+}
+
+impl ZeckendorfConfig {
+    pub fn configure<F: FieldExt>(cs: &mut ConstraintSystem<F>) -> Self {
+        let fib = Config::configure(cs);
+        // pins the seeds
+        let constants = cs.fixed_column();
+        cs.enable_constant(constants);
+        let q_first = cs.selector();
+        let q_step = cs.selector();
+
+        let one = || Expression::Constant(F::one());
+        cs.create_gate("zeckendorf first", |virtual_cells| {
+            let q_first = virtual_cells.query_selector(q_first);
+            let term = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let bit = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let acc = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            vec![
+                q_first.clone() * bit.clone() * (one() - bit.clone()),
+                q_first * (acc - bit * term),
+            ]
+        });
+
+        cs.create_gate("zeckendorf step", |virtual_cells| {
+            let q_step = virtual_cells.query_selector(q_step);
+            let term = virtual_cells.query_advice(fib.elem_1, Rotation::cur());
+            let bit = virtual_cells.query_advice(fib.elem_2, Rotation::cur());
+            let prev_bit = virtual_cells.query_advice(fib.elem_2, Rotation::prev());
+            let acc = virtual_cells.query_advice(fib.elem_3, Rotation::cur());
+            let prev_acc = virtual_cells.query_advice(fib.elem_3, Rotation::prev());
+            vec![
+                q_step.clone() * bit.clone() * (one() - bit.clone()),
+                q_step.clone() * bit.clone() * prev_bit,
+                q_step * (acc - prev_acc - bit * term),
+            ]
+        });
+
+        Self {
+            fib,
+            q_first,
+            q_step,
+        }
+    }
+}
+
+/// Circuit proving that the public `target` has a Zeckendorf representation, i.e. is a sum
+/// of non-consecutive terms of `1, 2, 3, 5, ...`.
+#[derive(Clone, Debug)]
+pub struct ZeckendorfCircuit {
+    target: u64,
+    witness: bool,
+}
+
+impl ZeckendorfCircuit {
+    pub fn new(target: u64) -> Self {
+        Self {
+            target,
+            witness: true,
+        }
+    }
+
+    /// Terms `1, 2, 3, 5, ...` up to the largest one not above the target (at least the
+    /// three laid out by the seed row).
+    fn terms(&self) -> Vec<u64> {
+        let (mut a, mut b) = (1u128, 2u128);
+        let mut terms = vec![];
+        while terms.len() < 3 || a <= self.target as u128 {
+            terms.push(a as u64);
+            (a, b) = (b, a + b);
+        }
+        terms
+    }
+
+    /// The greedy selection bits over [`Self::terms`], which never picks adjacent terms.
+    pub fn representation(&self) -> Vec<bool> {
+        let terms = self.terms();
+        let mut rest = self.target;
+        let mut bits = vec![false; terms.len()];
+        for (bit, &term) in bits.iter_mut().zip(&terms).rev() {
+            if term <= rest {
+                *bit = true;
+                rest -= term;
+            }
+        }
+        bits
+    }
+
+    fn k(&self) -> u32 {
+        // the sequence rows plus one row per term
+        let num_terms = self.terms().len();
+        k_for_rows(num_terms - 2 + num_terms)
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for ZeckendorfCircuit {
+    type Config = ZeckendorfConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            witness: false,
+            ..*self
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ZeckendorfConfig::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = |x: F| {
+            if self.witness {
+                Value::known(x)
+            } else {
+                Value::unknown()
+            }
+        };
+        let terms = config.fib.assign_terms(
+            layouter.namespace(|| "sequence"),
+            witness(F::one()),
+            witness(F::from(2)),
+            self.terms().len(),
+        )?;
+
+        let sum = layouter.assign_region(
+            || "zeckendorf",
+            |mut region| {
+                region.constrain_constant(terms[0].cell(), F::one())?;
+                region.constrain_constant(terms[1].cell(), F::from(2))?;
+
+                let mut acc = Value::known(F::zero());
+                let mut sum = None;
+                for (offset, (term, bit)) in terms.iter().zip(self.representation()).enumerate() {
+                    if offset == 0 {
+                        config.q_first.enable(&mut region, offset)?;
+                    } else {
+                        config.q_step.enable(&mut region, offset)?;
+                    }
+                    let term =
+                        term.copy_advice(|| "term", &mut region, config.fib.elem_1, offset)?;
+                    let bit = witness(F::from(bit as u64));
+                    region.assign_advice(|| "bit", config.fib.elem_2, offset, || bit)?;
+                    acc = acc + bit * term.value();
+                    sum =
+                        Some(region.assign_advice(|| "acc", config.fib.elem_3, offset, || acc)?);
+                }
+                Ok(sum.expect("at least three terms"))
+            },
+        )?;
+
+        config.fib.expose_public(layouter, &sum, 0)
+    }
+}
+
+/// Proves that `target` is a sum of non-consecutive Fibonacci numbers.
+pub fn prove_zeckendorf(target: u64) -> Result<FibonacciProof, FibError> {
+    let circuit = ZeckendorfCircuit::new(target);
+    let public_inputs = vec![Fp::from(target)];
+    let k = circuit.k();
+    let proof = create_circuit_proof(&circuit, &public_inputs, k)?;
+    Ok(FibonacciProof {
+        proof,
+        public_inputs,
+        num_terms: circuit.terms().len(),
+        k,
+    })
+}
+
+/// Verifies a proof produced by [`prove_zeckendorf`] for the same `target`.
+pub fn verify_zeckendorf(target: u64, proof: &FibonacciProof) -> Result<(), FibError> {
+    let circuit = ZeckendorfCircuit::new(target);
+    verify_circuit_proof(&circuit, &proof.public_inputs, &proof.proof, proof.k)
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::dev::MockProver;
+
+    use super::*;
+
+    #[test]
+    fn test_zeckendorf_100() {
+        // 100 = 89 + 8 + 3
+        let circuit = ZeckendorfCircuit::new(100);
+        let selected: Vec<u64> = circuit
+            .terms()
+            .into_iter()
+            .zip(circuit.representation())
+            .filter_map(|(term, bit)| bit.then_some(term))
+            .collect();
+        assert_eq!(selected, vec![3, 8, 89]);
+
+        let proof = prove_zeckendorf(100).unwrap();
+        assert_eq!(proof.public_inputs, vec![Fp::from(100)]);
+        verify_zeckendorf(100, &proof).unwrap();
+
+        let prover = MockProver::run(circuit.k(), &circuit, vec![vec![Fp::from(101)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
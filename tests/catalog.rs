@@ -0,0 +1,64 @@
+//! Every supported sequence, run through `MockProver` against its hand-computed 10th term.
+//! Adding a preset means adding a row here.
+
+use halo2_fibonacci::{FibonacciCircuit, RecurrenceCircuit, SignedCircuit};
+use halo2_proofs::dev::{MockProver, VerifyFailure};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::Circuit;
+
+const K: u32 = 5;
+
+fn run<C: Circuit<Fp>>(circuit: &C, instance: Vec<Fp>) -> Result<(), Vec<VerifyFailure>> {
+    MockProver::run(K, circuit, vec![instance])
+        .expect("synthesis succeeds")
+        .verify()
+}
+
+#[test]
+fn test_sequence_catalog() {
+    let recurrence = |(coef_1, coef_2), output| vec![Fp::from(coef_1), Fp::from(coef_2), output];
+
+    #[allow(clippy::type_complexity)]
+    let catalog: Vec<(
+        &str,
+        Box<dyn Fn(Vec<Fp>) -> Result<(), Vec<VerifyFailure>>>,
+        Vec<Fp>,
+    )> = vec![
+        (
+            "fibonacci",
+            Box::new(|instance| run(&FibonacciCircuit::<Fp>::new(1, 1, 10), instance)),
+            vec![Fp::from(55)],
+        ),
+        (
+            "lucas",
+            Box::new(|instance| run(&RecurrenceCircuit::<Fp>::lucas(10), instance)),
+            recurrence((1, 1), Fp::from(76)),
+        ),
+        (
+            "pell",
+            Box::new(|instance| run(&RecurrenceCircuit::<Fp>::pell(10), instance)),
+            recurrence((2, 1), Fp::from(985)),
+        ),
+        (
+            "jacobsthal",
+            Box::new(|instance| run(&RecurrenceCircuit::<Fp>::new((0, 1), (1, 2), 10), instance)),
+            recurrence((1, 2), Fp::from(171)),
+        ),
+        (
+            "alternating signs",
+            Box::new(|instance| run(&SignedCircuit::<Fp>::alternating(1, 1, 10), instance)),
+            // 1, 1, 2, -1, 1, -2, -1, -1, -2, 1
+            vec![Fp::from(1)],
+        ),
+    ];
+
+    for (name, run, expected) in catalog {
+        if let Err(failures) = run(expected.clone()) {
+            panic!("{}: {:?}", name, failures);
+        }
+
+        let mut wrong = expected;
+        *wrong.last_mut().unwrap() += Fp::one();
+        assert!(run(wrong).is_err(), "{}: accepted a wrong output", name);
+    }
+}
@@ -0,0 +1,29 @@
+//! Checks the circuit against an independent oracle, the `fibs` crate, for every index
+//! whose term fits in a `u128`. It shares no code with the crate's recurrence, so a bug
+//! would have to be made twice to slip through.
+
+use fibs::Fibonacci;
+use halo2_fibonacci::{fp_from_u128, is_satisfied, min_k, FibonacciCircuit};
+use halo2_proofs::pasta::Fp;
+
+/// The last index whose term fits in a `u128`.
+const MAX_U128_INDEX: usize = 186;
+
+#[test]
+fn test_matches_independent_oracle() {
+    // `fibs` counts from F(0) = 0, as the crate does
+    assert_eq!(Fibonacci::<u128>::f(90), Ok(2_880_067_194_370_816_120));
+    assert!(Fibonacci::<u128>::f(MAX_U128_INDEX + 1).is_err());
+
+    let k = min_k(MAX_U128_INDEX);
+    for n in 1..=MAX_U128_INDEX {
+        let expected = fp_from_u128(Fibonacci::f(n).unwrap());
+        let circuit = FibonacciCircuit::<Fp>::new(1, 1, n);
+        assert_eq!(circuit.output(), Some(expected), "F({})", n);
+
+        // the layout computes at least F(3)
+        if n >= 3 {
+            assert!(is_satisfied(&circuit, &[expected], k).unwrap(), "F({})", n);
+        }
+    }
+}
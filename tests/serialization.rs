@@ -0,0 +1,99 @@
+//! Proves, writes the proof to disk, and verifies it from a function that shares no
+//! in-memory state with the prover.
+//!
+//! halo2_proofs 0.2 cannot serialize verifying keys, so the file carries the key's
+//! fingerprint instead: the verifier rebuilds the params and the key from the circuit shape
+//! (`num_terms`, `k`) recorded in the proof, and checks it against the fingerprint before
+//! verifying.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use halo2_fibonacci::{
+    prove, prove_with_rng, verify, vk_fingerprint, FibError, FibonacciCircuit, FibonacciProof,
+};
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{keygen_vk, Circuit};
+use halo2_proofs::poly::commitment::Params;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use tempfile::NamedTempFile;
+
+/// The encoded proof of `F(10)` from seeds `1, 1` at `k = 5`, with blinding drawn from
+/// `SmallRng::seed_from_u64(42)`. Regenerate it with `UPDATE_GOLDEN=1 cargo test` only
+/// when the proof format is meant to change.
+const GOLDEN_PROOF: &str = "tests/fixtures/proof_fib10_k5_seed42.bin";
+
+/// The fingerprint of the verifying key for a `num_terms` circuit of size `2^k`.
+fn fingerprint(num_terms: usize, k: u32) -> [u8; 32] {
+    let params: Params<EqAffine> = Params::new(k);
+    let circuit = FibonacciCircuit::<Fp>::new(0, 0, num_terms).without_witnesses();
+    vk_fingerprint(&keygen_vk(&params, &circuit).unwrap())
+}
+
+/// Writes `vk fingerprint (32 bytes) | encoded proof`, the public inputs included.
+fn write_proof(file: &mut NamedTempFile) {
+    let proof = prove(&FibonacciCircuit::<Fp>::new(1, 1, 10), 5).unwrap();
+    file.write_all(&fingerprint(proof.num_terms, proof.k))
+        .unwrap();
+    file.write_all(&proof.to_bytes()).unwrap();
+    file.flush().unwrap();
+}
+
+fn read_and_verify(path: &Path) -> Result<FibonacciProof, FibError> {
+    let bytes = fs::read(path).unwrap();
+    let (vk, encoded) = bytes.split_at(32);
+    let proof = FibonacciProof::from_bytes(encoded)?;
+    if vk != fingerprint(proof.num_terms, proof.k) {
+        return Err(FibError::Verify);
+    }
+    verify(&proof)?;
+    Ok(proof)
+}
+
+#[test]
+fn test_proof_round_trips_through_a_file() {
+    let mut file = NamedTempFile::new().unwrap();
+    write_proof(&mut file);
+    let proof = read_and_verify(file.path()).unwrap();
+    assert_eq!(proof.public_inputs, vec![Fp::from(55)]);
+    assert_eq!((proof.num_terms, proof.k), (10, 5));
+
+    let bytes = fs::read(file.path()).unwrap();
+    let tamper = |offset: usize| {
+        let mut bytes = bytes.clone();
+        bytes[offset] ^= 1;
+        let mut tampered = NamedTempFile::new().unwrap();
+        tampered.write_all(&bytes).unwrap();
+        read_and_verify(tampered.path())
+    };
+    // a bit inside the fingerprint, then inside the output public input
+    assert!(matches!(tamper(0), Err(FibError::Verify)));
+    assert!(matches!(tamper(32 + 16), Err(FibError::Verify)));
+
+    assert!(matches!(
+        FibonacciProof::from_bytes(&bytes[32..52]),
+        Err(FibError::BadPublicInputs(_))
+    ));
+}
+
+#[test]
+fn test_proof_format_matches_golden_file() {
+    let circuit = FibonacciCircuit::<Fp>::new(1, 1, 10);
+    let proof = prove_with_rng(&circuit, 5, SmallRng::seed_from_u64(42)).unwrap();
+    let bytes = proof.to_bytes();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(GOLDEN_PROOF);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &bytes).unwrap();
+    }
+
+    let golden = fs::read(&path).unwrap();
+    assert!(
+        bytes == golden,
+        "the proof no longer matches {}; if the format change is deliberate, rerun with \
+         UPDATE_GOLDEN=1",
+        GOLDEN_PROOF
+    );
+    verify(&FibonacciProof::from_bytes(&golden).unwrap()).unwrap();
+}